@@ -0,0 +1,216 @@
+use crate::error::AppError;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Whether a file is named like an AppImage, as opposed to a plain binary or script
+pub fn is_appimage_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("AppImage"))
+}
+
+// The AppImage type from the magic bytes: 1 for the ISO9660-based format,
+// 2 for the current SquashFS one. Ok(None) if the file isn't an AppImage.
+pub fn appimage_type(path: &Path) -> io::Result<Option<u8>> {
+    let mut header = [0u8; 11];
+    File::open(path)?.read_exact(&mut header)?;
+    if &header[..4] != b"\x7fELF" || &header[8..10] != b"AI" {
+        return Ok(None);
+    }
+    Ok(Some(header[10]).filter(|kind| matches!(kind, 1 | 2)))
+}
+
+// The `<name>.zsync` file published next to an AppImage, if one was downloaded with it
+pub fn zsync_sidecar(path: &Path) -> Option<PathBuf> {
+    let mut name = path.file_name()?.to_os_string();
+    name.push(".zsync");
+    Some(path.with_file_name(name)).filter(|sidecar| sidecar.is_file())
+}
+
+// The URL: header of a zsync file, which tells where the full file is downloaded.
+// The header is text lines up to the first empty one; the block checksums follow
+// as binary data and aren't read. Ok(None) if there is no URL: line, or if it's
+// relative: that resolves against wherever the .zsync was downloaded from, which
+// a local copy doesn't know.
+pub fn read_zsync_url(path: &Path) -> io::Result<Option<String>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim_end();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        if let Some(url) = text.strip_prefix("URL:") {
+            return Ok(Some(url.trim().to_string()).filter(|url| url.contains("://")));
+        }
+    }
+}
+
+// Unpack the files matching a glob pattern with the AppImage's own runtime
+// (`--appimage-extract`), which writes them to `dest/squashfs-root`. Needs no FUSE,
+// but only type 2 AppImages support it. The pattern is passed through as is, so
+// paths taken from the image (which needn't be UTF-8) match exactly.
+pub fn extract(path: &Path, pattern: &OsStr, dest: &Path) -> Result<PathBuf, AppError> {
+    let output = Command::new(path)
+        .arg("--appimage-extract")
+        .arg(pattern)
+        .current_dir(dest)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::ExtractFailed(format!("{} {}", pattern.to_string_lossy(), output.status)));
+    }
+    Ok(dest.join("squashfs-root"))
+}
+
+// Read the update information AppImages embed in their ELF `.upd_info` section,
+// e.g. `gh-releases-zsync|owner|repo|latest|App-*x86_64.AppImage.zsync`.
+// Returns Ok(None) when the section is missing or empty.
+pub fn read_update_info(path: &Path) -> io::Result<Option<String>> {
+    let mut file = File::open(path)?;
+
+    let mut ident = [0u8; 16];
+    file.read_exact(&mut ident)?;
+    if &ident[..4] != b"\x7fELF" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not an ELF file"));
+    }
+    let is_64 = match ident[4] {
+        1 => false,
+        2 => true,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown ELF class")),
+    };
+    let little_endian = match ident[5] {
+        1 => true,
+        2 => false,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown ELF byte order")),
+    };
+    let reader = ElfReader { little_endian };
+
+    // Section header table location from the ELF header
+    let (shoff, shentsize, shnum, shstrndx) = if is_64 {
+        let mut header = [0u8; 48];
+        file.read_exact(&mut header)?;
+        (reader.u64(&header[24..32]), reader.u16(&header[42..44]), reader.u16(&header[44..46]), reader.u16(&header[46..48]))
+    } else {
+        let mut header = [0u8; 36];
+        file.read_exact(&mut header)?;
+        (reader.u32(&header[16..20]) as u64, reader.u16(&header[30..32]), reader.u16(&header[32..34]), reader.u16(&header[34..36]))
+    };
+    if shoff == 0 || shnum == 0 || shstrndx >= shnum {
+        return Ok(None);
+    }
+    // Entries must at least hold the fields read below; read_at caps the table size
+    let min_entry_size = if is_64 { 40 } else { 24 };
+    if (shentsize as usize) < min_entry_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "ELF section headers are too small"));
+    }
+
+    let table = read_at(&mut file, shoff, shentsize as u64 * shnum as u64)?;
+
+    // (name offset, file offset, size) of each section
+    let sections: Vec<(u32, u64, u64)> = table
+        .chunks_exact(shentsize as usize)
+        .map(|entry| {
+            if is_64 {
+                (reader.u32(&entry[0..4]), reader.u64(&entry[24..32]), reader.u64(&entry[32..40]))
+            } else {
+                (reader.u32(&entry[0..4]), reader.u32(&entry[16..20]) as u64, reader.u32(&entry[20..24]) as u64)
+            }
+        })
+        .collect();
+
+    let (_, names_offset, names_size) = sections[shstrndx as usize];
+    let names = read_at(&mut file, names_offset, names_size)?;
+
+    for &(name_offset, offset, size) in &sections {
+        let name = names
+            .get(name_offset as usize..)
+            .and_then(|rest| rest.split(|&b| b == 0).next())
+            .unwrap_or_default();
+        if name == b".upd_info" {
+            let data = read_at(&mut file, offset, size)?;
+            let info = String::from_utf8_lossy(&data)
+                .trim_matches(char::from(0))
+                .trim()
+                .to_string();
+            return Ok(if info.is_empty() { None } else { Some(info) });
+        }
+    }
+
+    Ok(None)
+}
+
+fn read_at(file: &mut File, offset: u64, size: u64) -> io::Result<Vec<u8>> {
+    // Section sizes come from the file itself, so don't trust them blindly
+    const MAX_SECTION_SIZE: u64 = 1024 * 1024;
+    if size > MAX_SECTION_SIZE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "ELF section is unexpectedly large"));
+    }
+    let mut data = vec![0u8; size as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut data)?;
+    Ok(data)
+}
+
+struct ElfReader {
+    little_endian: bool,
+}
+
+impl ElfReader {
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) }
+    }
+
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) }
+    }
+
+    fn u64(&self, bytes: &[u8]) -> u64 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&bytes[..8]);
+        if self.little_endian { u64::from_le_bytes(buf) } else { u64::from_be_bytes(buf) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn extract_passes_the_pattern_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        // Stands in for an AppImage runtime: records the pattern it was given
+        let runtime = dir.path().join("Fake.AppImage");
+        fs::write(&runtime, "#!/bin/sh\nprintf '%s' \"$2\" > pattern\nmkdir -p squashfs-root\n").unwrap();
+        fs::set_permissions(&runtime, fs::Permissions::from_mode(0o755)).unwrap();
+
+        // A space, a non-ASCII character and a byte that isn't valid UTF-8
+        let pattern = OsStr::from_bytes(b"usr/share/applications/My \xc3\x84pp \xff.desktop");
+        let root = extract(&runtime, pattern, dir.path()).unwrap();
+
+        assert_eq!(root, dir.path().join("squashfs-root"));
+        assert_eq!(fs::read(dir.path().join("pattern")).unwrap(), pattern.as_bytes());
+    }
+
+    #[test]
+    fn read_zsync_url_ignores_relative_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let sidecar = dir.path().join("Foo.AppImage.zsync");
+
+        fs::write(&sidecar, "zsync: 0.6.2\nURL: https://example.com/Foo.AppImage\n\n\x00\x01").unwrap();
+        assert_eq!(read_zsync_url(&sidecar).unwrap().as_deref(), Some("https://example.com/Foo.AppImage"));
+
+        fs::write(&sidecar, "zsync: 0.6.2\nURL: Foo.AppImage\n\n").unwrap();
+        assert_eq!(read_zsync_url(&sidecar).unwrap(), None);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::gui::{capitalize, DeskImageApp, Outcome};
+use crate::AppDirs;
+use clap::{Parser, Subcommand};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+// Command line interface; without a subcommand the GUI starts
+#[derive(Debug, Parser)]
+#[command(name = "deskimage", version, about = "Create desktop entries for AppImages")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Install an AppImage (or other executable) and create its desktop entry
+    Add {
+        #[arg(required_unless_present = "from")]
+        path: Option<PathBuf>,
+        /// Install with the options exported from the window (.deskimage.toml)
+        #[arg(long, value_name = "FILE", conflicts_with = "path")]
+        from: Option<PathBuf>,
+        /// Menu name, defaults to the cleaned-up file name
+        #[arg(long)]
+        name: Option<String>,
+        /// Custom icon (PNG, JPG or SVG)
+        #[arg(long)]
+        icon: Option<PathBuf>,
+        /// Semicolon separated, e.g. "Development;Utility;"
+        #[arg(long)]
+        categories: Option<String>,
+        /// Run in a terminal window
+        #[arg(long)]
+        terminal: bool,
+        /// Don't run update-desktop-database and gtk-update-icon-cache, e.g. when
+        /// adding many entries from a script and refreshing once at the end
+        #[arg(long)]
+        no_cache_update: bool,
+    },
+    /// Remove a desktop entry and its installed executable
+    Remove {
+        name: String,
+    },
+    /// List the entries DeskImage has installed
+    List,
+    /// Remove every entry DeskImage created, with its installed executable
+    #[command(alias = "uninstall-all")]
+    Purge {
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+// Run a subcommand and return the process exit code
+pub fn run(command: Command, app_dirs: AppDirs) -> i32 {
+    let mut app = DeskImageApp::new(app_dirs);
+
+    let result = match command {
+        Command::Add { path, from, name, icon, categories, terminal, no_cache_update } => {
+            app.set_skip_cache_update(no_cache_update);
+            match (from, path) {
+                (Some(from), _) => app.add_from_headless(&from, name, icon, categories, terminal),
+                (None, Some(path)) => app.add_headless(&path, name, icon, categories, terminal),
+                // clap requires one of them
+                (None, None) => unreachable!("add needs a path or --from"),
+            }
+        }
+        Command::Remove { name } => app.remove_headless(&name),
+        Command::List => {
+            for entry in app.installed_entries() {
+                println!("{}\t{}", entry.desktop.name, entry.desktop.exec);
+            }
+            return 0;
+        }
+        Command::Purge { yes } => {
+            let entries = app.purgeable_entries();
+            if entries.is_empty() {
+                println!("No entries created by DeskImage");
+                return 0;
+            }
+            for entry in &entries {
+                println!("{}\t{}", entry.desktop.name, entry.desktop.exec);
+            }
+            if !yes && !confirm(&format!("Remove these {} entries and their executables?", entries.len())) {
+                println!("Cancelled");
+                return 0;
+            }
+            app.purge_headless()
+        }
+    };
+
+    match result {
+        Ok(Outcome::Success(message)) => {
+            println!("SUCCESS: {}", message);
+            0
+        }
+        Ok(Outcome::Warning(message)) => {
+            println!("WARNING: {}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("ERROR: {}", capitalize(&e.to_string()));
+            1
+        }
+    }
+}
+
+// Ask a yes/no question on the terminal, defaulting to no
+fn confirm(question: &str) -> bool {
+    print!("{} [y/N] ", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
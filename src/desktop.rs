@@ -0,0 +1,411 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Version of the Desktop Entry Specification the generated files conform to.
+// This is the Version= key, not the version of the application itself.
+pub const DESKTOP_SPEC_VERSION: &str = "1.5";
+
+// The keys DeskImage reads and writes in a `.desktop` file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub generic_name: String,
+    pub exec: String,
+    // Written after the program in Exec= as is, so field codes like %U keep working
+    pub exec_args: String,
+    pub icon: String,
+    pub categories: Vec<String>,
+    pub keywords: Vec<String>,
+    pub mime_types: Vec<String>,
+    pub comment: String,
+    pub terminal: bool,
+    pub wm_class: String,
+    // The AppImage's version, kept in the X-AppImage-Version extension key
+    pub version: String,
+    // Where update tools can fetch the .zsync file, kept in X-AppImage-Zsync
+    pub zsync_url: String,
+    pub actions: Vec<DesktopAction>,
+    // Written as X-DeskImage-Installed=true, so purge can tell DeskImage's entries
+    // from other tools' that also carry X-AppImage-Version
+    pub deskimage_installed: bool,
+}
+
+// An extra launcher action shown in the icon's right-click menu,
+// written as a [Desktop Action <id>] group
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DesktopAction {
+    pub id: String,
+    pub name: String,
+    // Full command line, unlike DesktopEntry::exec which is just the program
+    pub exec: String,
+}
+
+impl DesktopEntry {
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let groups = parse_groups(content);
+        let empty = HashMap::new();
+        let group = |name: &str| {
+            groups.iter()
+                .find(|(group, _)| group == name)
+                .map(|(_, values)| values)
+                .unwrap_or(&empty)
+        };
+        let values = group("Desktop Entry");
+        let value = |key: &str| values.get(key).cloned().unwrap_or_default();
+        
+        let actions = split_list(&value("Actions"))
+            .into_iter()
+            .map(|id| {
+                let action = group(&format!("Desktop Action {}", id));
+                DesktopAction {
+                    name: action.get("Name").cloned().unwrap_or_default(),
+                    exec: action.get("Exec").cloned().unwrap_or_default(),
+                    id,
+                }
+            })
+            .collect();
+        let (exec, exec_args) = unescape_exec(&value("Exec"));
+        
+        Self {
+            name: value("Name"),
+            generic_name: value("GenericName"),
+            exec,
+            exec_args,
+            icon: value("Icon"),
+            categories: split_list(&value("Categories")),
+            keywords: split_list(&value("Keywords")),
+            mime_types: split_list(&value("MimeType")),
+            comment: value("Comment"),
+            terminal: value("Terminal") == "true",
+            wm_class: value("StartupWMClass"),
+            version: value("X-AppImage-Version"),
+            zsync_url: value("X-AppImage-Zsync"),
+            actions,
+            deskimage_installed: value("X-DeskImage-Installed") == "true",
+        }
+    }
+
+    pub fn write_to(&self, path: &Path) -> io::Result<()> {
+        write_desktop_file(path, &self.to_string())
+    }
+}
+
+// Write then rename so menus never pick up a half-written entry
+pub fn write_desktop_file(path: &Path, content: &str) -> io::Result<()> {
+    let tmp_path = path.with_extension("desktop.tmp");
+    if let Err(e) = fs::write(&tmp_path, content).and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+impl fmt::Display for DesktopEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Desktop Entry]")?;
+        writeln!(f, "Type=Application")?;
+        writeln!(f, "Version={}", DESKTOP_SPEC_VERSION)?;
+        writeln!(f, "Name={}", escape_value(&self.name))?;
+        let exec_args = self.exec_args.trim();
+        if exec_args.is_empty() {
+            writeln!(f, "Exec={}", escape_exec(&self.exec))?;
+        } else {
+            writeln!(f, "Exec={} {}", escape_exec(&self.exec), escape_value(exec_args))?;
+        }
+        writeln!(f, "Icon={}", escape_value(&self.icon))?;
+        writeln!(f, "Terminal={}", self.terminal)?;
+
+        // Optional keys are left out when empty
+        if !self.categories.is_empty() {
+            writeln!(f, "Categories={}", join_list(&self.categories))?;
+        }
+        if !self.keywords.is_empty() {
+            writeln!(f, "Keywords={}", join_list(&self.keywords))?;
+        }
+        if !self.mime_types.is_empty() {
+            writeln!(f, "MimeType={}", join_list(&self.mime_types))?;
+        }
+        let generic_name = self.generic_name.trim();
+        if !generic_name.is_empty() {
+            writeln!(f, "GenericName={}", escape_value(generic_name))?;
+        }
+        // Desktop entry values are single-line
+        let comment = self.comment.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !comment.is_empty() {
+            writeln!(f, "Comment={}", escape_value(&comment))?;
+        }
+        let wm_class = self.wm_class.trim();
+        if !wm_class.is_empty() {
+            writeln!(f, "StartupWMClass={}", escape_value(wm_class))?;
+        }
+        let version = self.version.trim();
+        if !version.is_empty() {
+            writeln!(f, "X-AppImage-Version={}", escape_value(version))?;
+        }
+        let zsync_url = self.zsync_url.trim();
+        if !zsync_url.is_empty() {
+            writeln!(f, "X-AppImage-Zsync={}", escape_value(zsync_url))?;
+        }
+        if self.deskimage_installed {
+            writeln!(f, "X-DeskImage-Installed=true")?;
+        }
+        
+        if !self.actions.is_empty() {
+            let ids: Vec<String> = self.actions.iter().map(|action| action.id.clone()).collect();
+            writeln!(f, "Actions={}", join_list(&ids))?;
+        }
+        for action in &self.actions {
+            writeln!(f)?;
+            writeln!(f, "[Desktop Action {}]", action.id)?;
+            writeln!(f, "Name={}", escape_value(&action.name))?;
+            writeln!(f, "Exec={}", escape_value(&action.exec))?;
+        }
+        Ok(())
+    }
+}
+
+// Keys every application entry needs that are missing from the [Desktop Entry] group
+pub fn missing_required_keys(content: &str) -> Vec<&'static str> {
+    let groups = parse_groups(content);
+    let values = groups.iter().find(|(name, _)| name == "Desktop Entry").map(|(_, values)| values);
+    ["Type", "Name", "Exec"]
+        .into_iter()
+        .filter(|key| !values.and_then(|values| values.get(*key)).is_some_and(|value| !value.is_empty()))
+        .collect()
+}
+
+// Parse a desktop entry file into its groups ("Desktop Entry", "Desktop Action new-window", ...)
+// and their key/value pairs, with string escapes resolved. Keys before any group
+// header land in a group named "Desktop Entry".
+pub fn parse_groups(content: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut groups = vec![("Desktop Entry".to_string(), HashMap::new())];
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            groups.push((name.to_string(), HashMap::new()));
+        } else if let Some(index) = line.find('=') {
+            let key = line[..index].trim().to_string();
+            let value = unescape_value(line[index + 1..].trim());
+            if let Some((_, values)) = groups.last_mut() {
+                values.insert(key, value);
+            }
+        }
+    }
+
+    // Fold the implicit leading group into the real [Desktop Entry] one
+    if let Some(index) = groups.iter().skip(1).position(|(name, _)| name == "Desktop Entry") {
+        let (_, leading) = groups.remove(0);
+        for (key, value) in leading {
+            groups[index].1.entry(key).or_insert(value);
+        }
+    }
+    groups
+}
+
+// Escape a string value: backslash escapes for backslash, newline, tab and
+// carriage return, and a leading space as \s so it isn't trimmed away
+pub fn escape_value(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            ' ' if i == 0 => escaped.push_str("\\s"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Undo escape_value. Other sequences (like \; in lists) are kept for the list parser.
+fn unescape_value(s: &str) -> String {
+    let mut value = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => value.push(' '),
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('\\') => value.push('\\'),
+            Some(other) => {
+                value.push('\\');
+                value.push(other);
+            }
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+// Turn a program path into an Exec= value. Arguments with reserved characters
+// are double-quoted (escaping ", `, $ and \ inside), % becomes %%, and the
+// result is escaped like any other string value.
+pub fn escape_exec(s: &str) -> String {
+    const RESERVED: &[char] = &[' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`'];
+
+    let mut arg = s.replace('%', "%%");
+    if arg.contains(RESERVED) {
+        let mut quoted = String::from("\"");
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        arg = quoted;
+    }
+    escape_value(&arg)
+}
+
+// Split an Exec= value (already string-unescaped) into the program path and
+// the arguments and field codes after it, which are kept verbatim
+fn unescape_exec(s: &str) -> (String, String) {
+    let mut program = String::new();
+    let mut chars = s.chars().peekable();
+    let quoted = chars.peek() == Some(&'"');
+    if quoted {
+        chars.next();
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => break,
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    program.push(escaped);
+                }
+            }
+            ' ' if !quoted => break,
+            '%' if chars.peek() == Some(&'%') => {
+                chars.next();
+                program.push('%');
+            }
+            c => program.push(c),
+        }
+    }
+    let args = chars.collect::<String>().trim().to_string();
+    (program, args)
+}
+
+// Split a semicolon-separated value (Categories=, Keywords=) into trimmed, unique,
+// non-empty items. An escaped \; is part of an item, not a separator.
+pub fn split_list(value: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&';') => {
+                chars.next();
+                current.push(';');
+            }
+            ';' => {
+                push_unique(&mut items, &current);
+                current.clear();
+            }
+            c => current.push(c),
+        }
+    }
+    push_unique(&mut items, &current);
+    items
+}
+
+fn push_unique(items: &mut Vec<String>, item: &str) {
+    let item = item.trim();
+    if !item.is_empty() && !items.iter().any(|i| i == item) {
+        items.push(item.to_string());
+    }
+}
+
+// Join items into a list value with the trailing semicolon the spec expects,
+// escaping semicolons and backslashes inside items
+pub fn join_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| format!("{};", escape_value(i).replace(';', "\\;")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Write an entry for a program and read it back
+    fn round_trip(exec: &str, exec_args: &str) -> DesktopEntry {
+        let entry = DesktopEntry {
+            name: "Foo".to_string(),
+            exec: exec.to_string(),
+            exec_args: exec_args.to_string(),
+            ..Default::default()
+        };
+        DesktopEntry::parse(&entry.to_string())
+    }
+
+    #[test]
+    fn escape_exec_quotes_paths_with_spaces() {
+        assert_eq!(escape_exec("/home/me/My Apps/Foo 1.0.AppImage"), "\"/home/me/My Apps/Foo 1.0.AppImage\"");
+        assert_eq!(escape_exec("/opt/foo/foo.AppImage"), "/opt/foo/foo.AppImage");
+    }
+
+    #[test]
+    fn escape_exec_escapes_reserved_characters() {
+        assert_eq!(escape_exec("/opt/100%/foo"), "/opt/100%%/foo");
+        assert_eq!(escape_exec("/opt/say \"hi\"/foo"), "\"/opt/say \\\\\"hi\\\\\"/foo\"");
+        assert_eq!(escape_exec("/opt/a\\b"), "\"/opt/a\\\\\\\\b\"");
+    }
+
+    #[test]
+    fn escape_value_keeps_a_leading_space() {
+        assert_eq!(escape_value(" Foo"), "\\sFoo");
+        assert_eq!(escape_value("Foo Bar"), "Foo Bar");
+        assert_eq!(escape_value("a\\b\nc"), "a\\\\b\\nc");
+    }
+
+    #[test]
+    fn exec_round_trips_through_parse() {
+        for exec in [
+            "/home/me/My Apps/Foo 1.0.AppImage",
+            "/opt/100%/foo",
+            "/opt/say \"hi\"/foo",
+            "/opt/a\\b/foo",
+            "/opt/$HOME/`foo`",
+        ] {
+            let parsed = round_trip(exec, "%U");
+            assert_eq!(parsed.exec, exec);
+            assert_eq!(parsed.exec_args, "%U");
+        }
+    }
+
+    #[test]
+    fn values_round_trip_through_parse() {
+        let entry = DesktopEntry {
+            name: " Foo".to_string(),
+            exec: "/opt/foo".to_string(),
+            comment: "50% off \"now\" with C:\\path".to_string(),
+            ..Default::default()
+        };
+        let parsed = DesktopEntry::parse(&entry.to_string());
+        assert_eq!(parsed.name, entry.name);
+        assert_eq!(parsed.comment, entry.comment);
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Failures of DeskImage's own operations. The window shows them as error
+// statuses, the command line prints them.
+#[derive(Debug)]
+pub enum AppError {
+    Io(io::Error),
+    // No home directory and no XDG override to install into
+    NoHome,
+    // The permissions were set but didn't stick, e.g. on a vfat or noexec mount
+    NotExecutable(PathBuf),
+    // The AppImage's --appimage-extract failed
+    ExtractFailed(String),
+    // The password dialog was dismissed or authentication failed
+    ElevationCancelled,
+    // The command run with pkexec or sudo failed
+    Elevation(String),
+    // Nothing to install: no file selected
+    NoSource,
+    NotFound(PathBuf),
+    // Strict mode's test launch failed; holds what the app printed
+    SmokeTestFailed(String),
+    // Not usable as a file name
+    InvalidName(String),
+    // An I/O failure on a specific file, e.g. "couldn't write <path>: ..."
+    File {
+        action: &'static str,
+        path: PathBuf,
+        source: io::Error,
+    },
+    // The user stopped the copy to this path
+    Cancelled(PathBuf),
+    // The desktop file was written but doesn't read back as a usable entry
+    Unverified(io::Error),
+    NoEntry(String),
+    // An options file that couldn't be read or parsed
+    InvalidOptions(PathBuf, String),
+    // One message per file that couldn't be removed
+    RemoveFailed(Vec<String>),
+    // Some entries of a purge were removed, the named ones weren't
+    PurgeIncomplete {
+        removed: usize,
+        skipped: usize,
+        failed: Vec<String>,
+    },
+}
+
+impl AppError {
+    pub fn file(action: &'static str, path: &Path, source: io::Error) -> Self {
+        AppError::File { action, path: path.to_path_buf(), source }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Io(e) => write!(f, "{}", e),
+            AppError::NoHome => write!(f, "couldn't determine the home directory, is HOME set?"),
+            AppError::NotExecutable(path) => write!(f, "{} is still not executable, its filesystem may not allow it", path.display()),
+            AppError::ExtractFailed(reason) => write!(f, "couldn't extract from the AppImage: {}", reason),
+            AppError::ElevationCancelled => write!(f, "authentication cancelled"),
+            AppError::Elevation(status) => write!(f, "command run as root failed ({})", status),
+            AppError::NoSource => write!(f, "no AppImage selected"),
+            AppError::NotFound(path) => write!(f, "file not found: {}", path.display()),
+            AppError::SmokeTestFailed(output) => write!(f, "the AppImage failed a test launch, no entry was created.\n{}", output),
+            AppError::InvalidName(name) => write!(f, "\"{}\" can't be used as a file name", name),
+            AppError::File { action, path, source } => write!(f, "couldn't {} {}: {}", action, path.display(), source),
+            AppError::Cancelled(path) => write!(f, "cancelled copying to {}", path.display()),
+            AppError::Unverified(e) => write!(f, "the desktop entry may not have been created properly: {}", e),
+            AppError::NoEntry(name) => write!(f, "no desktop entry named {}", name),
+            AppError::InvalidOptions(path, reason) => write!(f, "couldn't load options from {}: {}", path.display(), reason),
+            AppError::RemoveFailed(errors) => write!(f, "couldn't remove {}", errors.join(", ")),
+            AppError::PurgeIncomplete { removed, skipped, failed } => {
+                write!(f, "removed {} entries", removed)?;
+                if *skipped > 0 {
+                    write!(f, ", skipped {} not created by DeskImage", skipped)?;
+                }
+                write!(f, ", couldn't remove: {}", failed.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AppError::Io(e) | AppError::File { source: e, .. } | AppError::Unverified(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
@@ -1,21 +1,327 @@
+use crate::appimage;
+use crate::desktop::{self, join_list, split_list, DesktopAction, DesktopEntry};
+use crate::error::AppError;
+use crate::history::{self, HistoryRecord};
+use crate::options::{InstallMode, InstallOptions};
+use crate::paths;
+use crate::settings::Settings;
+use crate::AppDirs;
 use eframe::egui;
+use regex::Regex;
 use egui::{Color32, RichText, Stroke, Vec2};
+use std::collections::VecDeque;
+use std::ffi::OsStr;
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, LazyLock};
+use tracing::{debug, info, warn};
+
+// Where system-wide installs go, for all users
+const SYSTEM_BIN_DIR: &str = "/usr/local/bin";
+const SYSTEM_APPLICATIONS_DIR: &str = "/usr/share/applications";
+const SYSTEM_PIXMAPS_DIR: &str = "/usr/share/pixmaps";
+
+// Neutral status shown when nothing else is going on
+const DEFAULT_STATUS: &str = "Select an AppImage file to create a desktop entry";
+
+// How long a status stays up, fading out over its last STATUS_FADE
+const STATUS_DURATION: std::time::Duration = std::time::Duration::from_secs(10);
+const STATUS_FADE: std::time::Duration = std::time::Duration::from_secs(2);
+
+// Quiet time after the last change in the applications directory before rescanning it
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+// Filename patterns used when suggesting a name, compiled once since the menu
+// preview suggests one every frame
+static APPIMAGE_EXTENSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\.appimage$").unwrap());
+static PLATFORM_SUFFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)[-_. ](x86[-_]64|amd64|aarch64|arm64|armhf|armv7l|i[36]86|x64|linux(64)?)$").unwrap()
+});
+static VERSION_SUFFIX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)[-_ ]v?(\d+(\.\d+)+.*)$").unwrap());
+static NAME_SEPARATORS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[-_\s]+").unwrap());
+
+// Longest path shown in the path frames before it is shortened in the middle
+const MAX_PATH_CHARS: usize = 56;
+
+// Tools that refresh the menu after an entry changes, and the packages that provide them
+const CACHE_TOOLS: &[(&str, &str)] = &[
+    ("update-desktop-database", "desktop-file-utils"),
+    ("gtk-update-icon-cache", "gtk-update-icon-cache or gtk3"),
+];
+
+// Fixed-size PNG directories listed in hicolor's index.theme; icons elsewhere aren't found
+const HICOLOR_SIZES: &[u32] = &[16, 22, 24, 32, 48, 64, 96, 128, 256, 512];
+
+// Main categories from the freedesktop menu specification, offered in the picker
+const MAIN_CATEGORIES: &[&str] = &[
+    "AudioVideo", "Audio", "Video", "Development", "Education", "Game", "Graphics",
+    "Network", "Office", "Science", "Settings", "System", "Utility",
+];
+
+// Additional categories registered in the specification, accepted without a warning
+const ADDITIONAL_CATEGORIES: &[&str] = &[
+    "Building", "Debugger", "IDE", "GUIDesigner", "Profiling", "RevisionControl", "Translation",
+    "Calendar", "ContactManagement", "Database", "Dictionary", "Chart", "Email", "Finance",
+    "FlowChart", "PDA", "ProjectManagement", "Presentation", "Spreadsheet", "WordProcessor",
+    "2DGraphics", "VectorGraphics", "RasterGraphics", "3DGraphics", "Scanning", "OCR",
+    "Photography", "Publishing", "Viewer", "TextTools", "DesktopSettings", "HardwareSettings",
+    "Printing", "PackageManager", "Dialup", "InstantMessaging", "Chat", "IRCClient", "Feed",
+    "FileTransfer", "HamRadio", "News", "P2P", "RemoteAccess", "Telephony", "TelephonyTools",
+    "VideoConference", "WebBrowser", "WebDevelopment", "Midi", "Mixer", "Sequencer", "Tuner",
+    "TV", "AudioVideoEditing", "Player", "Recorder", "DiscBurning", "ActionGame",
+    "AdventureGame", "ArcadeGame", "BoardGame", "BlocksGame", "CardGame", "KidsGame",
+    "LogicGame", "RolePlaying", "Shooter", "Simulation", "SportsGame", "StrategyGame", "Art",
+    "Construction", "Music", "Languages", "ArtificialIntelligence", "Astronomy", "Biology",
+    "Chemistry", "ComputerScience", "DataVisualization", "Economy", "Electricity", "Geography",
+    "Geology", "Geoscience", "History", "Humanities", "ImageProcessing", "Literature", "Maps",
+    "Math", "NumericalAnalysis", "MedicalSoftware", "Physics", "Robotics", "Spirituality",
+    "Sports", "ParallelComputing", "Amusement", "Archiving", "Compression", "Electronics",
+    "Emulator", "Engineering", "FileTools", "FileManager", "TerminalEmulator", "Filesystem",
+    "Monitor", "Security", "Accessibility", "Calculator", "Clock", "TextEditor", "Documentation",
+    "Adult", "Core", "KDE", "GNOME", "XFCE", "DDE", "GTK", "Qt", "Motif", "Java", "ConsoleOnly",
+];
+
+// Kind of status message, which picks the banner colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatusLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl StatusLevel {
+    // Shown in front of the message, e.g. "ERROR: Couldn't write ..."
+    fn prefix(self) -> &'static str {
+        match self {
+            StatusLevel::Info => "",
+            StatusLevel::Success => "SUCCESS: ",
+            StatusLevel::Warning => "WARNING: ",
+            StatusLevel::Error => "ERROR: ",
+        }
+    }
+}
+
+// How an operation that went through ended. Failures are an AppError; the
+// window maps both to a status banner, the command line prints them.
+#[derive(Debug)]
+pub enum Outcome {
+    Success(String),
+    // Done, but with something the user should look at
+    Warning(String),
+}
+
+// Error messages are lowercase so they compose; a banner or line starts uppercase
+pub(crate) fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// Messages from the background copy thread
+enum CopyMessage {
+    Progress(u64, u64),
+    // Copying is done, the checksums are being compared
+    Verifying,
+    Finished(Result<u64, std::io::Error>),
+}
+
+// A copy running in the background, with what's needed to finish the entry afterwards
+struct CopyJob {
+    receiver: mpsc::Receiver<CopyMessage>,
+    appname: String,
+    exec_target: PathBuf,
+    started: std::time::Instant,
+    // Set from the UI; the copy thread stops at the next buffer and removes the partial file
+    cancel_flag: Arc<AtomicBool>,
+}
+
+// A folder of AppImages being installed one after another
+struct BatchJob {
+    queue: VecDeque<PathBuf>,
+    current: Option<PathBuf>,
+    // Whether the current file failed or was cancelled, set once it's done
+    current_failed: bool,
+    created: usize,
+    // Files whose entry already existed, left alone
+    skipped: usize,
+    failed: usize,
+}
+
+// Watches the applications directory so entries added or removed outside
+// DeskImage show up in the installed list
+struct ApplicationsWatch {
+    // Only kept alive; dropping it stops the watch
+    _watcher: notify::RecommendedWatcher,
+    receiver: mpsc::Receiver<()>,
+    // Latest change not yet picked up, for debouncing bursts of events
+    changed_at: Option<std::time::Instant>,
+}
+
+// What the last Create overwrote, so it can be put back
+struct UndoState {
+    appname: String,
+    desktop_file: PathBuf,
+    desktop_content: String,
+    binary: PathBuf,
+    // A snapshot of the replaced binary; None when the binary wasn't touched
+    binary_backup: Option<PathBuf>,
+}
+
+// The installed entry for the selected file, if it was installed before
+struct InstalledInfo {
+    desktop_path: PathBuf,
+    version: String,
+}
+
+impl From<InstalledEntry> for InstalledInfo {
+    fn from(entry: InstalledEntry) -> Self {
+        Self {
+            desktop_path: entry.path,
+            version: entry.desktop.version,
+        }
+    }
+}
+
+// Everything in the form the .desktop preview depends on
+#[derive(PartialEq)]
+struct PreviewKey {
+    options: Option<InstallOptions>,
+    install_dir: PathBuf,
+    system_wide: bool,
+    rename_on_collision: bool,
+    version: Option<String>,
+    zsync_url: Option<String>,
+}
+
+// What was unpacked from an AppImage: its icons and desktop file
+struct BundledContents {
+    dir: tempfile::TempDir,
+    icons: Vec<PathBuf>,
+    desktop: Option<DesktopEntry>,
+}
+
+// A desktop entry DeskImage installed, as found in the applications directory
+#[derive(Debug, Clone)]
+pub struct InstalledEntry {
+    pub path: PathBuf,
+    pub desktop: DesktopEntry,
+    // When DeskImage last installed it, from the history log (Unix seconds)
+    pub added_on: Option<u64>,
+    // Whether a copy is in the XDG autostart folder
+    pub autostart: bool,
+}
 
 pub struct DeskImageApp {
+    settings: Settings,
     appimage_path: Option<PathBuf>,
     icon_path: Option<PathBuf>,
-    status_message: String,
+    icon_texture: Option<egui::TextureHandle>,
+    // Icons found inside the selected AppImage, best first
+    bundled_icons: Vec<PathBuf>,
+    // Private directory they were unpacked into, removed when dropped
+    bundled_dir: Option<tempfile::TempDir>,
+    // Unpacking running in the background, with the file it's for
+    bundled_job: Option<(PathBuf, mpsc::Receiver<std::io::Result<BundledContents>>)>,
+    app_name: String,
+    install_dir: PathBuf,
+    install_mode: InstallMode,
+    // Install into /usr/local/bin and /usr/share/applications with elevated rights
+    system_wide: bool,
+    update_info: Option<String>,
+    // SHA-256 of the selected file, hashed on a background thread
+    checksum: Option<String>,
+    checksum_path: Option<PathBuf>,
+    checksum_receiver: Option<mpsc::Receiver<Result<String, std::io::Error>>>,
+    // Set to stop hashing a file that is no longer selected
+    checksum_cancel_flag: Arc<AtomicBool>,
+    categories: Vec<String>,
+    category_input: String,
+    generic_name: String,
+    comment: String,
+    keywords: String,
+    // File types to open with this app, semicolon separated
+    mime_types: String,
+    // Window class for taskbar grouping, written as StartupWMClass= when set
+    startup_wm_class: String,
+    exec_args: String,
+    // Reverse-DNS desktop file ID (com.example.App); names the .desktop file
+    // and binary instead of the app name when set
+    app_id: String,
+    // Parsed from the filename (e.g. "2.3.1" in App-2.3.1-x86_64.AppImage), read-only in the form
+    version: Option<String>,
+    // From a .zsync file next to the AppImage, or the installed entry's X-AppImage-Zsync
+    zsync_url: Option<String>,
+    store_zsync_url: bool,
+    file_preview_open: bool,
+    // The previewed file with the form state it was built from, rebuilt when that changes
+    file_preview: Option<(PreviewKey, String)>,
+    // Extra right-click menu actions
+    actions: Vec<DesktopAction>,
+    run_in_terminal: bool,
+    autostart: bool,
+    signal_on_completion: bool,
+    // Start the installed app once the current create succeeds ("Install and Launch")
+    launch_after_create: bool,
+    smoke_test: bool,
+    smoke_test_failed: bool,
+    skip_smoke_test_once: bool,
+    pending_create: bool,
+    copy_job: Option<CopyJob>,
+    copy_progress: Option<f32>,
+    skip_cache_update: bool,
+    keep_original_filename: bool,
+    // Name of the installed binary; empty for the default from install_bin_name
+    bin_name: String,
+    rename_on_collision: bool,
+    installed_entries: Vec<InstalledEntry>,
+    // Set when the selected file is already installed; Create becomes Update
+    installed_state: Option<InstalledInfo>,
+    // Hash comparison still running for installed_state
+    installed_receiver: Option<mpsc::Receiver<Option<InstalledEntry>>>,
+    // Search text for the installed apps list, matched against Name= and Exec=
+    installed_filter: String,
+    batch: Option<BatchJob>,
+    batch_log: Vec<(StatusLevel, String)>,
+    // Desktop file that would be overwritten, waiting for the user to confirm
+    pending_overwrite: Option<PathBuf>,
+    // Full text of the last error, shown in a dismissable window
+    error_modal: Option<String>,
+    // The window was closed while work was running, ask before quitting
+    close_prompt: bool,
+    // Quitting was confirmed; close once the cancelled work has cleaned up
+    quit_when_idle: bool,
+    // "Remove all" was clicked, waiting for the user to confirm
+    purge_prompt: bool,
+    status: (StatusLevel, String),
     is_installed: bool,
+    fuse_available: bool,
+    // appimageupdatetool is on PATH, so in-place updates can be offered
+    update_tool_available: bool,
+    // A running appimageupdatetool and the AppImage it's updating
+    update_job: Option<(PathBuf, mpsc::Receiver<Result<std::process::Output, std::io::Error>>)>,
+    // Window class detection running for the Detect button
+    wm_class_job: Option<mpsc::Receiver<Result<String, String>>>,
+    // Strict mode's test launch of this file; Create runs again once it passes
+    smoke_test_job: Option<(PathBuf, mpsc::Receiver<Result<(), String>>)>,
     status_visible: bool,
     status_timestamp: std::time::Instant,
+    // Show raw status internals under the banner, enabled with DESKIMAGE_DEBUG=1
+    debug_mode: bool,
+    app_dirs: AppDirs,
+    applications_watch: Option<ApplicationsWatch>,
+    // Desktop file and installed binary of the last successful create, for the open-folder buttons
+    last_created: Option<(PathBuf, PathBuf)>,
+    undo_state: Option<UndoState>,
 }
 
-impl Default for DeskImageApp {
-    fn default() -> Self {
+impl DeskImageApp {
+    pub fn new(app_dirs: AppDirs) -> Self {
         // Check if already installed globally
         let current_exe = std::env::current_exe().unwrap_or_default();
         let target_path = Path::new("/usr/local/bin/deskimage");
@@ -23,637 +329,3676 @@ impl Default for DeskImageApp {
         // Check both if we're running from /usr/local/bin/deskimage
         // or if the file exists there (for when we're running from cargo or another location)
         let is_installed = current_exe == target_path || target_path.exists();
+        
+        // Type 2 AppImages need FUSE (libfuse2) to run or be mounted
+        let fuse_available = detect_fuse();
+        info!("FUSE available: {}", fuse_available);
+        let update_tool_available = command_exists("appimageupdatetool");
+        debug!("appimageupdatetool available: {}", update_tool_available);
+        
+        let debug_mode = std::env::var("DESKIMAGE_DEBUG").is_ok_and(|value| value == "1");
 
-        Self {
+        let settings = Settings::load();
+        let install_dir = settings.install_dir.clone()
+            .unwrap_or_else(|| app_dirs.bin.clone());
+
+        let mut app = Self {
+            settings,
             appimage_path: None,
             icon_path: None,
-            status_message: "Select an AppImage file to create a desktop entry".to_string(),
+            icon_texture: None,
+            bundled_icons: Vec::new(),
+            bundled_dir: None,
+            bundled_job: None,
+            app_name: String::new(),
+            install_dir,
+            install_mode: InstallMode::Copy,
+            system_wide: false,
+            update_info: None,
+            checksum: None,
+            checksum_path: None,
+            checksum_receiver: None,
+            checksum_cancel_flag: Arc::new(AtomicBool::new(false)),
+            categories: vec!["Utility".to_string()],
+            category_input: String::new(),
+            generic_name: String::new(),
+            comment: String::new(),
+            keywords: String::new(),
+            mime_types: String::new(),
+            startup_wm_class: String::new(),
+            exec_args: String::new(),
+            app_id: String::new(),
+            version: None,
+            zsync_url: None,
+            store_zsync_url: false,
+            file_preview_open: false,
+            file_preview: None,
+            actions: Vec::new(),
+            run_in_terminal: false,
+            autostart: false,
+            signal_on_completion: false,
+            launch_after_create: false,
+            smoke_test: false,
+            smoke_test_failed: false,
+            skip_smoke_test_once: false,
+            pending_create: false,
+            copy_job: None,
+            copy_progress: None,
+            skip_cache_update: false,
+            keep_original_filename: false,
+            bin_name: String::new(),
+            rename_on_collision: false,
+            installed_entries: Vec::new(),
+            installed_state: None,
+            installed_receiver: None,
+            installed_filter: String::new(),
+            batch: None,
+            batch_log: Vec::new(),
+            pending_overwrite: None,
+            error_modal: None,
+            close_prompt: false,
+            quit_when_idle: false,
+            purge_prompt: false,
+            status: (StatusLevel::Info, DEFAULT_STATUS.to_string()),
             is_installed,
+            fuse_available,
+            update_tool_available,
+            update_job: None,
+            wm_class_job: None,
+            smoke_test_job: None,
             status_visible: true,
             status_timestamp: std::time::Instant::now(),
+            debug_mode,
+            app_dirs,
+            applications_watch: None,
+            last_created: None,
+            undo_state: None,
+        };
+        app.refresh_installed_entries();
+        
+        // Say once why new entries may not show up in the menu right away
+        let missing_tools: Vec<String> = CACHE_TOOLS.iter()
+            .filter(|(tool, _)| !command_exists(tool))
+            .map(|(tool, package)| format!("{} (from {})", tool, package))
+            .collect();
+        if !missing_tools.is_empty() {
+            app.update_status(StatusLevel::Warning, format!(
+                "{} not found. The menu may need a re-login to show new entries; install {} to refresh it right away.",
+                missing_tools.join(" and "),
+                if missing_tools.len() == 1 { "it" } else { "them" },
+            ));
         }
+        app
     }
 }
 
 impl DeskImageApp {
     // Add a helper method to update status messages
-    fn update_status(&mut self, message: String) {
-        println!("Status update: {}", message);
-        self.status_message = message;
+    fn update_status(&mut self, level: StatusLevel, message: String) {
+        info!("Status update: {}{}", level.prefix(), message);
+        // Errors are often too long for the banner; a folder install collects them in its log instead
+        if level == StatusLevel::Error && self.batch.is_none() {
+            self.error_modal = Some(message.clone());
+        }
+        self.status = (level, message);
         self.status_timestamp = std::time::Instant::now();
         self.status_visible = true;
     }
 
+    // Opacity of the status banner: fully opaque until the last STATUS_FADE of
+    // STATUS_DURATION, then fading out linearly. The neutral message and progress never fade.
+    fn status_alpha(&self) -> f32 {
+        if !self.status_visible || self.status.1 == DEFAULT_STATUS || self.work_in_progress() {
+            return 1.0;
+        }
+        let fade_start = STATUS_DURATION - STATUS_FADE;
+        let fading_for = self.status_timestamp.elapsed().saturating_sub(fade_start);
+        1.0 - (fading_for.as_secs_f32() / STATUS_FADE.as_secs_f32()).min(1.0)
+    }
+
+    // Name of the installed binary: the one typed under Advanced, else the original
+    // filename if asked to keep it, else the safe form of the app name. Name= stays
+    // the friendly name either way.
+    fn install_bin_name(&self, appname: &str, original_name: &str) -> String {
+        let custom = self.bin_name.trim();
+        if custom.is_empty() {
+            self.default_bin_name(appname, original_name)
+        } else {
+            custom.to_string()
+        }
+    }
+    
+    fn default_bin_name(&self, appname: &str, original_name: &str) -> String {
+        if self.keep_original_filename {
+            original_name.trim_end_matches(".AppImage").to_string()
+        } else {
+            safe_filename(appname)
+        }
+    }
+    
+    // Whether Exec= points at the selected file itself. Plain executables and scripts
+    // are launched from where they are when linking, since they may rely on files next to them.
+    fn launches_in_place(&self, path: &Path) -> bool {
+        match self.install_mode {
+            InstallMode::InPlace => true,
+            InstallMode::Symlink => !appimage::is_appimage_file(path),
+            InstallMode::Copy => false,
+        }
+    }
+
+    // A creation, background copy or folder install is running
+    fn work_in_progress(&self) -> bool {
+        self.pending_create || self.copy_job.is_some() || self.smoke_test_job.is_some() || self.batch.is_some()
+    }
+
     fn install_globally(&mut self) {
         let current_exe = std::env::current_exe().unwrap_or_default();
         let target_path = Path::new("/usr/local/bin/deskimage");
 
-        let status = Command::new("sudo")
-            .arg("cp")
-            .arg(&current_exe)
-            .arg(target_path)
-            .status();
-
-        match status {
-            Ok(status) if status.success() => {
-                self.update_status("SUCCESS: Installed to /usr/local/bin. Now you can run `deskimage` globally.".to_string());
-                self.is_installed = true;
+        match copy_with_privilege(&current_exe, target_path) {
+            Ok(()) => {
+                // The copy can report success without the file having landed (e.g. on a read-only
+                // mount), so check the target really matches before declaring success
+                let source_len = fs::metadata(&current_exe).map(|m| m.len()).ok();
+                match fs::metadata(target_path) {
+                    Ok(metadata) if Some(metadata.len()) == source_len => {
+                        self.update_status(StatusLevel::Success, "Installed to /usr/local/bin. Now you can run `deskimage` globally.".to_string());
+                        self.is_installed = true;
+                    }
+                    Ok(_) => {
+                        self.update_status(StatusLevel::Error, format!("{} was written incompletely. Is /usr/local/bin on a full or read-only filesystem?",
+                            target_path.display()));
+                    }
+                    Err(e) => {
+                        self.update_status(StatusLevel::Error, format!("Copy reported success but {} is missing ({}). /usr/local/bin may be read-only or managed by your distribution.",
+                            target_path.display(), e));
+                    }
+                }
             }
-            _ => {
-                self.update_status("ERROR: Failed to install. Are you sure you have sudo permissions?".to_string());
+            Err(AppError::ElevationCancelled) => {
+                self.update_status(StatusLevel::Error, "Authentication cancelled, DeskImage was not installed globally.".to_string());
+            }
+            Err(AppError::Elevation(status)) => {
+                self.update_status(StatusLevel::Error, format!("Copy to {} failed ({}). /usr/local/bin may not be writable on this system.",
+                    target_path.display(), status));
+            }
+            Err(e) => {
+                self.update_status(StatusLevel::Error, format!("Couldn't run pkexec or sudo: {}", e));
             }
         }
     }
     
-    fn select_appimage(&mut self) -> bool {
-        if let Some(path) = rfd::FileDialog::new()
+    // Persist the current settings, keeping only a log message on failure
+    fn save_settings(&mut self) {
+        self.settings.install_dir = Some(self.install_dir.clone());
+        if let Err(e) = self.settings.save() {
+            warn!("Couldn't save settings: {}", e);
+        }
+    }
+    
+    fn select_executable(&mut self) -> bool {
+        let mut dialog = rfd::FileDialog::new()
             .add_filter("AppImage", &["AppImage"])
-            .pick_file() {
+            .add_filter("All executables", &["AppImage", "sh", "run", "bin"]);
+        if let Some(dir) = &self.settings.last_appimage_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        
+        match dialog.pick_file() {
+            Some(path) => {
+                self.select_path(path);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    // Use a file picked from the dialog or the recent files list
+    fn select_path(&mut self, path: PathBuf) {
+        self.settings.last_appimage_dir = path.parent().map(Path::to_path_buf);
+        self.settings.remember_recent_file(path.clone());
+        
+        // Make the AppImage executable when it's selected
+        if !self.is_executable(&path) {
+            info!("AppImage is not executable, setting executable permissions");
             
-            // Make the AppImage executable when it's selected
-            if !self.is_executable(&path) {
-                println!("AppImage is not executable, setting executable permissions");
-                
-                if let Err(e) = self.make_executable(&path) {
-                    println!("Warning: Couldn't set permissions on source AppImage: {}", e);
-                    self.update_status(format!("WARNING: Couldn't make AppImage executable: {}", e));
-                } else {
-                    // Verify the AppImage is now executable
-                    if self.is_executable(&path) {
-                        println!("Successfully made AppImage executable: {}", path.display());
-                    } else {
-                        println!("Warning: AppImage may not be executable despite permissions change");
-                        self.update_status(format!("WARNING: AppImage may not be executable despite permissions change"));
-                    }
+            match self.make_executable(&path) {
+                Ok(()) => info!("Successfully made AppImage executable: {}", path.display()),
+                Err(e) => {
+                    warn!("Couldn't set permissions on source AppImage: {}", e);
+                    self.update_status(StatusLevel::Warning, format!("Couldn't make AppImage executable: {}", e));
                 }
-            } else {
-                println!("AppImage is already executable: {}", path.display());
             }
-            
-            self.appimage_path = Some(path.clone());
-            self.update_status(format!("Selected: {}", path.display()));
-            true
         } else {
-            false
+            debug!("AppImage is already executable: {}", path.display());
+        }
+        
+        self.appimage_path = Some(path.clone());
+        self.app_id.clear();
+        
+        // Plain binaries and scripts have no AppImage header or update information
+        let is_appimage = appimage::is_appimage_file(&path);
+        self.update_info = if is_appimage {
+            match appimage::read_update_info(&path) {
+                Ok(info) => info,
+                Err(e) => {
+                    warn!("Couldn't read update information: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let appimage_type = if is_appimage {
+            appimage::appimage_type(&path).ok().flatten()
+        } else {
+            None
+        };
+        match appimage_type {
+            None if is_appimage => {
+                warn!("{} has no AppImage magic bytes", path.display());
+                self.update_status(StatusLevel::Warning, format!("{} doesn't look like an AppImage, it may not run", path.display()));
+            }
+            // Type 1 images predate --appimage-extract and embedded update information
+            Some(1) => {
+                info!("{} is a type 1 AppImage", path.display());
+                self.update_status(StatusLevel::Info, format!("Selected: {} (type 1 AppImage, no embedded icon or update support)", path.display()));
+            }
+            Some(kind) => self.update_status(StatusLevel::Info, format!("Selected: {} (type {} AppImage)", path.display(), kind)),
+            None => self.update_status(StatusLevel::Info, format!("Selected: {}", path.display())),
         }
+        // Bundled icons and details are only unpacked on request, since that runs the file
+        self.clear_bundled();
+        self.app_name = self.default_app_name(&path);
+        self.load_existing_entry();
+        
+        self.refresh_installed_state();
+        self.announce_installed_state();
     }
     
-    fn select_icon(&mut self) -> bool {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Icons", &["png", "svg", "xpm", "jpg", "jpeg"])
-            .pick_file() {
-            self.icon_path = Some(path.clone());
-            self.update_status(format!("Selected icon: {}", path.display()));
-            true
-        } else {
-            false
+    // Say the selection is already installed; warnings from selecting it stay visible
+    fn announce_installed_state(&mut self) {
+        let installed_message = self.installed_state.as_ref()
+            .filter(|_| self.status.0 == StatusLevel::Info)
+            .map(|installed| if installed.version.is_empty() {
+                "Already installed (click Update to replace it)".to_string()
+            } else {
+                format!("Already installed, version {} (click Update to replace it)", installed.version)
+            });
+        if let Some(message) = installed_message {
+            self.update_status(StatusLevel::Info, message);
         }
     }
     
-    // Parse a desktop entry file to extract key values
-    fn parse_desktop_file(&self, content: &str) -> std::collections::HashMap<String, String> {
-        let mut values = std::collections::HashMap::new();
+    // Look for a managed entry whose binary is the selected file: the same file,
+    // one with the same name, or a copy with the same SHA-256. Hashing runs on a
+    // thread and is picked up by poll_installed_state.
+    fn refresh_installed_state(&mut self) {
+        self.installed_receiver = None;
+        self.installed_state = None;
+        let Some(source) = self.appimage_path.clone() else {
+            return;
+        };
         
-        for line in content.lines() {
-            if let Some(index) = line.find('=') {
-                let key = line[..index].trim().to_string();
-                let value = line[index+1..].trim().to_string();
-                values.insert(key, value);
-            }
+        let entries = self.purgeable_entries();
+        let same_file = entries.iter().find(|entry| {
+            let binary = Path::new(&entry.desktop.exec);
+            fs::canonicalize(binary).is_ok_and(|binary| binary == source)
+                || binary.file_name().is_some_and(|name| Some(name) == source.file_name())
+        });
+        if let Some(entry) = same_file {
+            info!("{} is already installed as {}", source.display(), entry.path.display());
+            self.installed_state = Some(InstalledInfo::from(entry.clone()));
+            return;
         }
         
-        values
+        // Copies renamed after the app can only be told apart by content; only hash
+        // those with the same size
+        let source_size = fs::metadata(&source).map(|m| m.len()).ok();
+        let candidates: Vec<InstalledEntry> = entries.into_iter()
+            .filter(|entry| fs::metadata(&entry.desktop.exec).map(|m| m.len()).ok() == source_size)
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let source_checksum = self.checksum.clone().filter(|_| self.checksum_path.as_ref() == Some(&source));
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(find_copy_by_checksum(&source, source_checksum, candidates));
+        });
+        self.installed_receiver = Some(receiver);
+    }
+    
+    fn poll_installed_state(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.installed_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(found) => {
+                self.installed_receiver = None;
+                if let Some(entry) = found {
+                    info!("Selected file is already installed as {}", entry.path.display());
+                    self.installed_state = Some(InstalledInfo::from(entry));
+                    self.announce_installed_state();
+                }
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            Err(mpsc::TryRecvError::Disconnected) => self.installed_receiver = None,
+        }
     }
     
-    fn create_desktop_entry(&mut self) {
-        println!("Creating desktop entry...");
+    // Unpack the selected AppImage's icons and desktop file on a thread;
+    // poll_bundled_job applies them once they're ready
+    fn start_bundled_extraction(&mut self) {
+        let Some(path) = self.appimage_path.clone() else {
+            return;
+        };
+        if appimage::appimage_type(&path).ok().flatten() != Some(2) {
+            self.update_status(StatusLevel::Warning, "Only type 2 AppImages can be unpacked".to_string());
+            return;
+        }
         
-        if let Some(appimage_path) = &self.appimage_path {
-            if !appimage_path.exists() {
-                println!("File not found: {}", appimage_path.display());
-                self.update_status(format!("ERROR: File not found: {}", appimage_path.display()));
+        info!("Unpacking icons and desktop file from {}", path.display());
+        let (sender, receiver) = mpsc::channel();
+        let source = path.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(extract_bundled(&source));
+        });
+        self.bundled_job = Some((path, receiver));
+        self.update_status(StatusLevel::Info, "Reading icons and details from the AppImage...".to_string());
+    }
+    
+    fn poll_bundled_job(&mut self, ctx: &egui::Context) {
+        let Some((path, receiver)) = &self.bundled_job else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
                 return;
             }
-
-            let original_name = match appimage_path.file_name() {
-                Some(name) => name.to_string_lossy(),
-                None => {
-                    println!("Invalid file path: no filename");
-                    self.update_status("ERROR: Invalid file path: no filename".to_string());
-                    return;
+            Err(mpsc::TryRecvError::Disconnected) => Err(std::io::Error::other("unpacking stopped unexpectedly")),
+        };
+        let path = path.clone();
+        self.bundled_job = None;
+        
+        match result {
+            Ok(contents) => self.apply_bundled_contents(&path, contents),
+            Err(e) => {
+                warn!("Couldn't unpack {}: {}", path.display(), e);
+                self.update_status(StatusLevel::Error, format!("Couldn't unpack {}: {}", path.display(), e));
+            }
+        }
+    }
+    
+    // Offer the unpacked icons and fill the form from the bundled desktop file,
+    // without overwriting anything already typed in or installed
+    fn apply_bundled_contents(&mut self, path: &Path, contents: BundledContents) {
+        self.bundled_icons = contents.icons;
+        self.bundled_dir = Some(contents.dir);
+        
+        if let Some(bundled) = contents.desktop {
+            // The AppImage's own desktop file names the app better than its filename does,
+            // unless the name was edited or the app was installed under the filename-based name
+            let default_name = self.default_app_name(path);
+            let installed_by_filename = self.desktop_file_path(&default_name).is_some_and(|path| path.exists());
+            let bundled_name = bundled.name.trim();
+            if self.app_name == default_name && !bundled_name.is_empty() && !installed_by_filename {
+                self.app_name = bundled_name.to_string();
+                self.load_existing_entry();
+            }
+            
+            // An installed entry was loaded and wins over the bundled defaults
+            let installed = self.entry_id()
+                .and_then(|appname| self.desktop_file_path(&appname))
+                .is_some_and(|path| path.exists());
+            if !installed {
+                info!("Using defaults from the AppImage's desktop file");
+                if !bundled.categories.is_empty() && self.categories == ["Utility"] {
+                    self.categories = bundled.categories;
                 }
-            };
-            let appname = self.clean_app_name(&original_name);
-            println!("App name: {}", appname);
-
-            match dirs::home_dir() {
-                Some(home_dir) => {
-                    let exec_target = home_dir.join(".local/bin").join(&appname);
-                    
-                    // Create directory if it doesn't exist
-                    match fs::create_dir_all(exec_target.parent().unwrap()) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("Couldn't create directory: {}", e);
-                            self.update_status(format!("ERROR: Couldn't create directory {}: {}", 
-                                exec_target.parent().unwrap().display(), e));
-                            return;
-                        }
-                    }
-                    
-                    // First, make sure the source AppImage is executable
-                    if !self.is_executable(appimage_path) {
-                        println!("Source AppImage is not executable, setting executable permissions");
-                        if let Err(e) = self.make_executable(appimage_path) {
-                            println!("Warning: Couldn't make source AppImage executable: {}", e);
-                            // Continue anyway, we'll set permissions on the target
-                        }
-                    } else {
-                        println!("Source AppImage is already executable");
-                    }
-                    
-                    // Then copy it to the target location
-                    match fs::copy(appimage_path, &exec_target) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("Couldn't copy file: {}", e);
-                            self.update_status(format!("ERROR: Couldn't copy file to {}: {}", 
-                                exec_target.display(), e));
-                            return;
-                        }
-                    }
-                    
-                    // Set executable permissions on the destination file
-                    match self.make_executable(&exec_target) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("Couldn't set permissions: {}", e);
-                            self.update_status(format!("ERROR: Couldn't set permissions on {}: {}", 
-                                exec_target.display(), e));
-                            return;
-                        }
-                    }
-
-                    // First try XDG_DATA_HOME, then fallback to ~/.local/share
-                    let applications_dir = match dirs::data_dir() {
-                        Some(dir) => dir.join("applications"),
-                        None => home_dir.join(".local/share/applications"),
-                    };
-                    
-                    println!("Applications directory: {}", applications_dir.display());
-                    
-                    // Ensure the applications directory exists
-                    match fs::create_dir_all(&applications_dir) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("Couldn't create applications directory: {}", e);
-                            self.update_status(format!("ERROR: Couldn't create applications directory {}: {}", 
-                                applications_dir.display(), e));
-                            return;
-                        }
-                    }
-                    
-                    let desktop_file_path = applications_dir.join(format!("{}.desktop", appname));
-                    println!("Desktop file path: {}", desktop_file_path.display());
-                    
-                    // Check if the desktop entry already exists before we start
-                    let desktop_existed = desktop_file_path.exists();
-                    println!("Desktop file existed before: {}", desktop_existed);
-                    
-                    // Check if desktop entry already exists
-                    let mut existing_icon = String::from("application-x-executable");
-                    let mut existing_keywords = String::new();
-                    let mut existing_categories = String::from("Utility;");
-                    let mut existing_comment = String::new();
-                    
-                    if desktop_file_path.exists() {
-                        if let Ok(content) = fs::read_to_string(&desktop_file_path) {
-                            let values = self.parse_desktop_file(&content);
-                            
-                            // Preserve the custom icon if it exists and no new one is selected
-                            if let Some(icon) = values.get("Icon") {
-                                existing_icon = icon.clone();
-                            }
-                            
-                            // Preserve keywords
-                            if let Some(keywords) = values.get("Keywords") {
-                                existing_keywords = keywords.clone();
-                            }
-                            
-                            // Preserve categories but ensure "Utility" is included
-                            if let Some(categories) = values.get("Categories") {
-                                if !categories.is_empty() {
-                                    existing_categories = categories.clone();
-                                    if !existing_categories.contains("Utility") {
-                                        existing_categories = format!("Utility;{}", existing_categories);
-                                    }
-                                    // Ensure it ends with semicolon
-                                    if !existing_categories.ends_with(';') {
-                                        existing_categories.push(';');
-                                    }
-                                }
-                            }
-                            
-                            // Preserve comment/description
-                            if let Some(comment) = values.get("Comment") {
-                                existing_comment = comment.clone();
-                            }
-                        }
-                    }
-                    
-                    // Handle custom icon if selected
-                    let icon_value = if let Some(icon_path) = &self.icon_path {
-                        // Copy the icon to the local icons directory if it exists
-                        if icon_path.exists() {
-                            let icon_filename = icon_path.file_name().unwrap().to_string_lossy();
-                            let icon_path_string = icon_path.to_string_lossy().to_string();
-                            let icon_destination = home_dir
-                                .join(".local/share/icons")
-                                .join(&*icon_filename);
-                            
-                            // Create icons directory if it doesn't exist
-                            let icon_result = icon_path_string.clone();
-                            if let Err(e) = fs::create_dir_all(icon_destination.parent().unwrap()) {
-                                println!("Couldn't create icons directory: {}", e);
-                                let warning = format!("WARNING: Couldn't create icons directory: {}", e);
-                                self.update_status(warning);
-                                // Continue with the original path as fallback
-                                icon_result
-                            } else {
-                                // Copy the icon file
-                                if let Err(e) = fs::copy(icon_path, &icon_destination) {
-                                    println!("Couldn't copy icon: {}", e);
-                                    let warning = format!("WARNING: Couldn't copy icon: {}", e);
-                                    self.update_status(warning);
-                                    // Continue with the original path as fallback
-                                    icon_result
-                                } else {
-                                    // Use the icon destination path
-                                    icon_destination.to_string_lossy().to_string()
-                                }
-                            }
-                        } else {
-                            // Icon doesn't exist, fall back to default
-                            existing_icon
-                        }
-                    } else {
-                        // No new icon selected, use existing
-                        existing_icon
-                    };
-                    
-                    // Create desktop entry content with preserved or new icon value
-                    let mut desktop_content = format!(
-                        "[Desktop Entry]\nType=Application\nName={}\nExec={}\nIcon={}\nTerminal=false\n",
-                        appname,
-                        exec_target.to_string_lossy(),
-                        icon_value
-                    );
-                    
-                    // Add optional fields if they exist
-                    if !existing_categories.is_empty() {
-                        desktop_content.push_str(&format!("Categories={}\n", existing_categories));
-                    }
-                    
-                    if !existing_keywords.is_empty() {
-                        desktop_content.push_str(&format!("Keywords={}\n", existing_keywords));
-                    }
-                    
-                    if !existing_comment.is_empty() {
-                        desktop_content.push_str(&format!("Comment={}\n", existing_comment));
+                let keywords = join_list(&bundled.keywords);
+                for (field, value) in [
+                    (&mut self.generic_name, bundled.generic_name),
+                    (&mut self.comment, bundled.comment),
+                    (&mut self.keywords, keywords),
+                    (&mut self.startup_wm_class, bundled.wm_class),
+                ] {
+                    if field.trim().is_empty() {
+                        *field = value;
                     }
-                    
-                    // Write the desktop file
-                    match fs::write(&desktop_file_path, desktop_content) {
-                        Ok(_) => {
-                            println!("Successfully wrote desktop file");
-                        },
-                        Err(e) => {
-                            println!("Couldn't write desktop file: {}", e);
-                            self.update_status(format!("ERROR: Couldn't write desktop file {}: {}", 
-                                desktop_file_path.display(), e));
-                            return;
-                        }
-                    }
-                    
-                    // Attempt to update the desktop database to make it immediately visible
-                    println!("Updating desktop database...");
-                    match Command::new("update-desktop-database")
-                        .arg(applications_dir.to_string_lossy().to_string())
-                        .status() {
-                        Ok(status) => println!("update-desktop-database exited with: {}", status),
-                        Err(e) => println!("Failed to run update-desktop-database: {}", e),
-                    };
-
-                    // Update the icon cache using gtk-update-icon-cache if available
-                    println!("Updating icon cache...");
-                    match Command::new("gtk-update-icon-cache")
-                        .arg("-f")
-                        .arg("-t")
-                        .arg(home_dir.join(".local/share/icons"))
-                        .status() {
-                        Ok(status) => println!("gtk-update-icon-cache exited with: {}", status),
-                        Err(e) => println!("Failed to run gtk-update-icon-cache: {}", e),
-                    };
-
-                    // Verify the desktop entry was created successfully
-                    match fs::metadata(&desktop_file_path) {
-                        Ok(_) => {
-                            println!("Successfully verified desktop entry exists");
-                            let message = if desktop_existed {
-                                format!("SUCCESS: Desktop entry updated at: {}", desktop_file_path.display())
-                            } else {
-                                format!("SUCCESS: Desktop entry created at: {}", desktop_file_path.display())
-                            };
-                            println!("Setting status message: {}", message);
-                            self.update_status(message);
-                        },
-                        Err(e) => {
-                            println!("Failed to verify desktop entry: {}", e);
-                            self.update_status(format!("ERROR: Desktop entry may not have been created properly. Error: {}", e));
-                        }
-                    }
-                },
-                None => {
-                    self.update_status("❌ Couldn't find home directory.".to_string());
                 }
             }
-        } else {
-            self.update_status("❌ No AppImage selected.".to_string());
         }
+        
+        let message = match self.bundled_icons.len() {
+            0 => "No icons found in the AppImage".to_string(),
+            1 => "Found 1 icon in the AppImage".to_string(),
+            count => format!("Found {} icons in the AppImage", count),
+        };
+        self.update_status(StatusLevel::Info, message);
     }
-
-    fn clean_app_name(&self, filename: &str) -> String {
-        let base = filename
-            .trim_end_matches(".AppImage")
-            .split(|c: char| c == '-' || c == '_')
-            .next()
-            .unwrap_or(filename);
-        base.to_string()
-    }
-
-    // Helper function to check if a file is executable
-    fn is_executable<P: AsRef<Path>>(&self, path: P) -> bool {
-        if let Ok(metadata) = fs::metadata(&path) {
-            let permissions = metadata.permissions();
-            let mode = permissions.mode();
-            return mode & 0o111 != 0; // Check if any executable bit is set
+    
+    // Forget what was unpacked for the previous file and remove its directory
+    fn clear_bundled(&mut self) {
+        if let Some(dir) = self.bundled_dir.take() {
+            if self.icon_path.as_ref().is_some_and(|icon| icon.starts_with(dir.path())) {
+                self.icon_path = None;
+                self.icon_texture = None;
+            }
         }
-        false
-    }
-
-    // Helper function to make a file executable
-    fn make_executable<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
-        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        self.bundled_icons.clear();
+        self.bundled_job = None;
     }
-}
-
-impl eframe::App for DeskImageApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Configure the UI style for a modern look
-        let mut style = (*ctx.style()).clone();
-        style.spacing.item_spacing = Vec2::new(10.0, 15.0);
-        style.spacing.window_margin = Vec2::new(24.0, 24.0).into();
-        
-        // Dark theme
-        style.visuals.dark_mode = true;
-        style.visuals.panel_fill = Color32::from_rgb(22, 22, 30);
-        style.visuals.window_fill = Color32::from_rgb(22, 22, 30);
-        style.visuals.faint_bg_color = Color32::from_rgb(35, 35, 45);
-        style.visuals.extreme_bg_color = Color32::from_rgb(15, 15, 20);
+    
+    // Queue every AppImage in a folder for installation
+    fn select_appimage_folder(&mut self) -> bool {
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(dir) = &self.settings.last_appimage_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(folder) = dialog.pick_folder() else {
+            return false;
+        };
         
-        // Button styles
-        style.visuals.widgets.inactive.bg_fill = Color32::from_rgb(50, 50, 65);
-        style.visuals.widgets.hovered.bg_fill = Color32::from_rgb(70, 70, 90);
-        style.visuals.widgets.active.bg_fill = Color32::from_rgb(90, 90, 120);
-        style.visuals.widgets.inactive.bg_stroke = Stroke::new(1.0, Color32::from_rgb(80, 80, 100));
+        let mut appimages: Vec<PathBuf> = match fs::read_dir(&folder) {
+            Ok(dir) => dir
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("AppImage")))
+                .collect(),
+            Err(e) => {
+                warn!("Couldn't read folder {}: {}", folder.display(), e);
+                self.update_status(StatusLevel::Error, format!("Couldn't read {}: {}", folder.display(), e));
+                return false;
+            }
+        };
+        if appimages.is_empty() {
+            self.update_status(StatusLevel::Warning, format!("No AppImages found in {}", folder.display()));
+            return false;
+        }
+        appimages.sort();
         
-        // Apply the style
-        ctx.set_style(style);
+        info!("Installing {} AppImages from {}", appimages.len(), folder.display());
+        self.settings.last_appimage_dir = Some(folder);
+        self.batch_log.clear();
+        self.update_status(StatusLevel::Info, format!("Processing {} AppImages...", appimages.len()));
+        self.batch = Some(BatchJob {
+            queue: appimages.into(),
+            current: None,
+            current_failed: false,
+            created: 0,
+            skipped: 0,
+            failed: 0,
+        });
+        true
+    }
+    
+    // Record the outcome of the previous batch file and start the next one.
+    // Each file goes through the same create_desktop_entry pipeline as a single pick.
+    fn advance_batch(&mut self, ctx: &egui::Context) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+        if self.pending_create || self.copy_job.is_some() || self.smoke_test_job.is_some() {
+            return;
+        }
         
-        // Store current status to detect changes
-        let previous_status = self.status_message.clone();
+        if let Some(path) = batch.current.take() {
+            let filename = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let (level, message) = &self.status;
+            if std::mem::take(&mut batch.current_failed) {
+                batch.failed += 1;
+                self.batch_log.push((*level, format!("{}: {}{}", filename, level.prefix(), message)));
+            } else {
+                batch.created += 1;
+                self.batch_log.push((StatusLevel::Success, format!("{}: created", filename)));
+            }
+        }
+        
+        let Some(path) = batch.queue.pop_front() else {
+            let Some(batch) = self.batch.take() else {
+                return;
+            };
+            let level = if batch.failed > 0 { StatusLevel::Warning } else { StatusLevel::Success };
+            self.update_status(level, format!("{} created, {} skipped, {} failed", batch.created, batch.skipped, batch.failed));
+            self.save_settings();
+            self.signal_completion(ctx, "desktop entries for the folder");
+            return;
+        };
+        batch.current = Some(path.clone());
+        
+        self.app_id.clear();
+        self.app_name = self.default_app_name(&path);
+        if !self.is_executable(&path) {
+            if let Err(e) = self.make_executable(&path) {
+                warn!("Couldn't make {} executable: {}", path.display(), e);
+            }
+        }
+        self.update_info = appimage::read_update_info(&path).ok().flatten();
+        self.appimage_path = Some(path.clone());
+        self.icon_path = None;
+        self.icon_texture = None;
+        self.clear_bundled();
+        self.load_existing_entry();
+        // Nobody is there to confirm an overwrite, so leave existing entries alone
+        if let Some(existing) = self.overwrite_target() {
+            info!("Skipping {}, {} already exists", path.display(), existing.display());
+            if let Some(batch) = &mut self.batch {
+                let filename = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                self.batch_log.push((StatusLevel::Warning, format!("{}: skipped, {} already exists", filename, existing.display())));
+                batch.skipped += 1;
+                batch.current = None;
+            }
+            ctx.request_repaint();
+            return;
+        }
+        if let Some(result) = self.create_desktop_entry().transpose() {
+            self.record_batch_result(&result);
+            self.report(result);
+        }
+        ctx.request_repaint();
+    }
+    
+    // Note how the current folder file ended; a cancelled copy counts as failed
+    fn record_batch_result(&mut self, result: &Result<Outcome, AppError>) {
+        if let Some(batch) = &mut self.batch {
+            batch.current_failed = result.is_err();
+        }
+    }
+    
+    // Per-file results of the last folder install
+    fn show_batch_log(&self, ui: &mut egui::Ui) {
+        if self.batch_log.is_empty() {
+            return;
+        }
+        ui.label(RichText::new("Folder install log:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+        egui::ScrollArea::vertical()
+            .id_salt("batch_log")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                for (level, line) in &self.batch_log {
+                    let color = if *level == StatusLevel::Error {
+                        Color32::from_rgb(255, 100, 100)
+                    } else {
+                        Color32::from_rgb(180, 180, 180)
+                    };
+                    ui.label(RichText::new(line).size(12.0).color(color));
+                }
+            });
+        ui.add_space(20.0);
+    }
+    
+    fn select_install_dir(&mut self) -> bool {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_directory(&self.install_dir)
+            .pick_folder() {
+            self.install_dir = path;
+            self.save_settings();
+            self.update_status(StatusLevel::Info, format!("Install directory: {}", self.install_dir.display()));
+            true
+        } else {
+            false
+        }
+    }
+    
+    fn select_icon(&mut self, ctx: &egui::Context) -> bool {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("Icons", &["png", "svg", "xpm", "jpg", "jpeg"]);
+        if let Some(dir) = &self.settings.last_icon_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        
+        if let Some(path) = dialog.pick_file() {
+            self.settings.last_icon_dir = path.parent().map(Path::to_path_buf);
+            self.icon_path = Some(path.clone());
+            self.load_icon_texture(ctx);
+            self.update_status(StatusLevel::Info, format!("Selected icon: {}", path.display()));
+            true
+        } else {
+            false
+        }
+    }
+    
+    // Rasterize the selected icon into a thumbnail texture, or clear it if that fails
+    fn load_icon_texture(&mut self, ctx: &egui::Context) {
+        self.icon_texture = match self.icon_path.as_deref().map(load_icon_thumbnail) {
+            Some(Ok(image)) => Some(ctx.load_texture("icon-thumbnail", image, egui::TextureOptions::LINEAR)),
+            Some(Err(e)) => {
+                warn!("Couldn't load icon preview: {}", e);
+                None
+            }
+            None => None,
+        };
+    }
+    
+    // Path of the desktop file an app name maps to. Entries written before names
+    // were sanitized keep their raw filename.
+    fn desktop_file_path(&self, appname: &str) -> Option<PathBuf> {
+        let legacy = self.app_dirs.applications.join(format!("{}.desktop", appname));
+        if !appname.contains('/') && legacy.exists() {
+            return Some(legacy);
+        }
+        Some(self.app_dirs.applications.join(desktop_file_name(appname)))
+    }
+    
+    // Path of the autostart copy of an entry, honoring XDG_CONFIG_HOME
+    fn autostart_file_path(&self, appname: &str) -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("autostart").join(desktop_file_name(appname)))
+    }
+    
+    // Add or remove the autostart copy of an entry to match its checkbox
+    fn sync_autostart(&self, appname: &str, desktop_content: &str, enabled: bool) -> Result<(), std::io::Error> {
+        let autostart_path = match self.autostart_file_path(appname) {
+            Some(path) => path,
+            None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Could not determine config directory")),
+        };
+        
+        if enabled {
+            info!("Writing autostart entry: {}", autostart_path.display());
+            if let Some(parent) = autostart_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            desktop::write_desktop_file(&autostart_path, desktop_content)
+        } else if autostart_path.exists() {
+            info!("Removing autostart entry: {}", autostart_path.display());
+            fs::remove_file(&autostart_path)
+        } else {
+            Ok(())
+        }
+    }
+    
+    // Name used for Name=, the desktop file and the binary; falls back to the
+    // cleaned filename when the name field is left empty
+    fn effective_app_name(&self) -> Option<String> {
+        let name = self.app_name.trim();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+        Some(self.default_app_name(self.appimage_path.as_ref()?))
+    }
+    
+    // Stem of the desktop file (and default binary name): the app ID if one is set,
+    // otherwise the app name
+    fn entry_id(&self) -> Option<String> {
+        let app_id = self.app_id.trim();
+        if app_id.is_empty() {
+            self.effective_app_name()
+        } else {
+            Some(app_id.to_string())
+        }
+    }
+    
+    // Name suggested for a file: AppImages get their version and platform suffixes
+    // cleaned up, other executables just lose their extension
+    fn default_app_name(&self, path: &Path) -> String {
+        if appimage::is_appimage_file(path) {
+            let filename = path.file_name().unwrap_or_default().to_string_lossy();
+            clean_app_name(&filename)
+        } else {
+            path.file_stem().unwrap_or_default().to_string_lossy().to_string()
+        }
+    }
+    
+    // First of "app", "app-2", "app-3"... whose desktop file is free or already
+    // belongs to the selected AppImage
+    fn unique_app_name(&self, base: &str) -> String {
+        let Some(source) = &self.appimage_path else {
+            return base.to_string();
+        };
+        let source = fs::canonicalize(source).unwrap_or_else(|_| source.clone());
+        
+        for n in 1.. {
+            let candidate = if n == 1 { base.to_string() } else { format!("{}-{}", base, n) };
+            let Some(path) = self.desktop_file_path(&candidate) else {
+                return candidate;
+            };
+            let Ok(existing) = DesktopEntry::from_file(&path) else {
+                return candidate;
+            };
+            if existing.exec.is_empty() {
+                return candidate;
+            }
+            let exec = PathBuf::from(existing.exec);
+            // A symlink resolves to the source, a copy matches it in size and time
+            let same_app = fs::canonicalize(&exec).is_ok_and(|exec| exec == source)
+                || is_unchanged_copy(&source, &exec);
+            if same_app {
+                return candidate;
+            }
+        }
+        unreachable!()
+    }
+    
+    // Pre-fill the form from an existing entry with the current app name, if any
+    fn load_existing_entry(&mut self) {
+        self.categories = vec!["Utility".to_string()];
+        self.generic_name.clear();
+        self.comment.clear();
+        self.keywords.clear();
+        self.mime_types.clear();
+        self.startup_wm_class.clear();
+        self.exec_args.clear();
+        self.actions.clear();
+        self.bin_name.clear();
+        self.run_in_terminal = false;
+        self.autostart = false;
+        self.version = self.appimage_path.as_deref().and_then(version_from_path);
+        self.zsync_url = self.appimage_path.as_deref()
+            .and_then(appimage::zsync_sidecar)
+            .and_then(|sidecar| match appimage::read_zsync_url(&sidecar) {
+                Ok(url) => url,
+                Err(e) => {
+                    warn!("Couldn't read {}: {}", sidecar.display(), e);
+                    None
+                }
+            });
+        self.store_zsync_url = false;
+        
+        let appname = match self.entry_id() {
+            Some(appname) => appname,
+            None => return,
+        };
+        
+        self.autostart = self.autostart_file_path(&appname)
+            .is_some_and(|autostart_path| autostart_path.exists());
+        
+        if let Some(existing) = self.desktop_file_path(&appname)
+            .and_then(|path| DesktopEntry::from_file(&path).ok()) {
+            if !existing.categories.is_empty() {
+                info!("Loaded categories from existing entry: {:?}", existing.categories);
+                self.categories = existing.categories;
+            }
+            self.generic_name = existing.generic_name;
+            self.comment = existing.comment;
+            self.keywords = join_list(&existing.keywords);
+            self.mime_types = join_list(&existing.mime_types);
+            self.startup_wm_class = existing.wm_class;
+            self.exec_args = existing.exec_args;
+            self.actions = existing.actions;
+            self.run_in_terminal = existing.terminal;
+            // An installed copy named after the app has lost the version from its filename
+            let editing_installed = self.appimage_path.as_deref() == Some(Path::new(&existing.exec));
+            if self.version.is_none() && editing_installed && !existing.version.is_empty() {
+                self.version = Some(existing.version);
+            }
+            // Keep a stored URL on update; a sidecar next to the new file takes precedence
+            if !existing.zsync_url.is_empty() {
+                self.zsync_url.get_or_insert(existing.zsync_url);
+                self.store_zsync_url = true;
+            }
+        }
+    }
+    
+    // Add categories typed into the chip input (semicolon separated), skipping duplicates
+    fn add_categories_from_input(&mut self) {
+        for category in split_list(&self.category_input) {
+            if !self.categories.contains(&category) {
+                self.categories.push(category);
+            }
+        }
+        self.category_input.clear();
+    }
+    
+    // Multi-select menu for the main categories, sharing the chip list
+    fn show_main_category_picker(&mut self, ui: &mut egui::Ui) {
+        let selected: Vec<&str> = MAIN_CATEGORIES.iter()
+            .copied()
+            .filter(|main| self.categories.iter().any(|c| c == main))
+            .collect();
+        let selected_text = if selected.is_empty() {
+            "Choose main categories".to_string()
+        } else {
+            selected.join(", ")
+        };
+        
+        egui::ComboBox::from_id_salt("main_categories")
+            .selected_text(selected_text)
+            .width(250.0)
+            .show_ui(ui, |ui| {
+                for main in MAIN_CATEGORIES {
+                    let mut checked = self.categories.iter().any(|c| c == main);
+                    if ui.checkbox(&mut checked, *main).changed() {
+                        if checked {
+                            self.categories.push(main.to_string());
+                        } else {
+                            self.categories.retain(|c| c != main);
+                        }
+                    }
+                }
+            });
+    }
+    
+    // Chip editor for the Categories= value
+    fn show_category_chips(&mut self, ui: &mut egui::Ui) {
+        let mut remove_index = None;
+        let mut move_left_index = None;
+        
+        ui.horizontal_wrapped(|ui| {
+            for (index, category) in self.categories.iter().enumerate() {
+                egui::Frame::new()
+                    .fill(Color32::from_rgb(45, 55, 75))
+                    .corner_radius(12)
+                    .stroke(Stroke::new(1.0, Color32::from_rgb(70, 90, 130)))
+                    .inner_margin(egui::Margin::symmetric(8, 2))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 4.0;
+                            if index > 0 && ui.small_button("◀").on_hover_text("Move earlier").clicked() {
+                                move_left_index = Some(index);
+                            }
+                            ui.label(RichText::new(category).size(13.0));
+                            if ui.small_button("✕").on_hover_text("Remove category").clicked() {
+                                remove_index = Some(index);
+                            }
+                        });
+                    });
+            }
+        });
+        
+        if let Some(index) = move_left_index {
+            self.categories.swap(index - 1, index);
+        }
+        if let Some(index) = remove_index {
+            self.categories.remove(index);
+        }
+        
+        ui.horizontal(|ui| {
+            let input = ui.add(egui::TextEdit::singleline(&mut self.category_input)
+                .hint_text("Add category, e.g. TextEditor")
+                .desired_width(200.0));
+            let submitted = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                self.add_categories_from_input();
+            }
+        });
+        
+        // Show the exact value that will be written
+        ui.label(RichText::new(format!("Categories={}", join_list(&self.categories))).monospace().size(12.0));
+        
+        // Unknown categories are allowed but most menus will ignore them
+        let unknown: Vec<&str> = self.categories.iter()
+            .map(|c| c.as_str())
+            .filter(|c| !c.starts_with("X-") && !MAIN_CATEGORIES.contains(c) && !ADDITIONAL_CATEGORIES.contains(c))
+            .collect();
+        if !unknown.is_empty() {
+            ui.label(RichText::new(format!("WARNING: Not registered categories: {} (use an X- prefix for custom ones)", unknown.join(", ")))
+                .size(12.0)
+                .color(Color32::from_rgb(255, 220, 150)));
+        }
+    }
+    
+    // Install the selected file and write its entry. Ok(None) means a background
+    // copy was started; poll_copy_job reports how it ends.
+    fn create_desktop_entry(&mut self) -> Result<Option<Outcome>, AppError> {
+        if let Some(appimage_path) = &self.appimage_path {
+            // Group every message of this install under one span
+            let _span = tracing::info_span!("install", app = tracing::field::Empty, source = %appimage_path.display()).entered();
+            info!("Creating desktop entry...");
+
+            if !appimage_path.exists() {
+                warn!("File not found: {}", appimage_path.display());
+                return Err(AppError::NotFound(appimage_path.clone()));
+            }
+            
+            // In strict mode, refuse to create a launcher for an AppImage that crashes on start
+            let skip_smoke_test = std::mem::take(&mut self.skip_smoke_test_once);
+            self.smoke_test_failed = false;
+            if self.smoke_test && !skip_smoke_test {
+                // The launch takes seconds, so it runs on a thread and poll_smoke_test
+                // comes back here once it passed
+                let (sender, receiver) = mpsc::channel();
+                let path = appimage_path.clone();
+                let thread_path = path.clone();
+                std::thread::spawn(move || {
+                    let _ = sender.send(smoke_test_launch(&thread_path));
+                });
+                self.update_status(StatusLevel::Info, format!("Test-launching {}...", path.display()));
+                self.smoke_test_job = Some((path, receiver));
+                return Ok(None);
+            }
+
+            let original_name = match appimage_path.file_name() {
+                Some(name) => name.to_string_lossy(),
+                None => {
+                    warn!("Invalid file path: no filename");
+                    return Err(AppError::InvalidName(appimage_path.display().to_string()));
+                }
+            };
+            let app_id = self.app_id.trim();
+            let mut appname = if !app_id.is_empty() {
+                // The window disables Create for this; the command line gets here
+                if !is_valid_app_id(app_id) {
+                    warn!("{} is not a valid reverse-DNS desktop file ID", app_id);
+                    return Err(AppError::InvalidName(app_id.to_string()));
+                }
+                app_id.to_string()
+            } else if self.app_name.trim().is_empty() {
+                self.default_app_name(appimage_path)
+            } else {
+                self.app_name.trim().to_string()
+            };
+            if self.rename_on_collision {
+                let unique = self.unique_app_name(&appname);
+                if unique != appname {
+                    info!("{}.desktop belongs to another app, using {} instead", appname, unique);
+                    appname = unique;
+                }
+            }
+            tracing::Span::current().record("app", appname.as_str());
+            debug!("App name: {}", appname);
+
+            if !is_valid_bin_name(self.bin_name.trim()) {
+                return Err(AppError::InvalidName(self.bin_name.trim().to_string()));
+            }
+            let bin_name = self.install_bin_name(&appname, &original_name);
+            if self.system_wide {
+                let source = appimage_path.clone();
+                return self.install_system_wide(&appname, &bin_name, &source).map(Some);
+            }
+            
+            let is_appimage = appimage::is_appimage_file(appimage_path);
+            let exec_target = if self.launches_in_place(appimage_path) {
+                appimage_path.clone()
+            } else {
+                self.install_dir.join(&bin_name)
+            };
+            
+            // Create directory if it doesn't exist
+            if let Err(e) = fs::create_dir_all(&self.install_dir) {
+                warn!("Couldn't create directory: {}", e);
+                return Err(AppError::file("create directory", &self.install_dir, e));
+            }
+            
+            // Fail early rather than halfway through a large copy
+            if let Err(e) = check_dir_writable(&self.install_dir) {
+                warn!("Install directory is not writable: {}", e);
+                return Err(AppError::file("write to", &self.install_dir, e));
+            }
+            
+            // First, make sure the source AppImage is executable
+            if !self.is_executable(appimage_path) {
+                info!("Source AppImage is not executable, setting executable permissions");
+                if let Err(e) = self.make_executable(appimage_path) {
+                    warn!("Couldn't make source AppImage executable: {}", e);
+                    // Continue anyway, we'll set permissions on the target
+                }
+            } else {
+                debug!("Source AppImage is already executable");
+            }
+            
+            // Then copy it to the target location. Re-running Create just to change the
+            // icon or categories shouldn't recopy an identical binary.
+            let already_linked = match (fs::canonicalize(appimage_path), fs::canonicalize(&exec_target)) {
+                (Ok(source), Ok(target)) => source == target,
+                _ => false,
+            };
+            let identical = !already_linked && self.install_mode != InstallMode::Symlink && is_unchanged_copy(appimage_path, &exec_target);
+            
+            // Overwriting an existing entry: keep the old desktop file and binary for Undo
+            let previous_entry = self.desktop_file_path(&appname)
+                .and_then(|path| fs::read_to_string(&path).ok().map(|content| (path, content)));
+            if let Some((desktop_file, desktop_content)) = previous_entry {
+                if let Some(previous) = self.undo_state.take() {
+                    discard_undo_backup(&previous);
+                }
+                let replaces_binary = !already_linked && !identical && fs::symlink_metadata(&exec_target).is_ok();
+                // The old binary stays in place until the new one is renamed over it,
+                // so a failed or cancelled copy leaves the existing install working
+                let binary_backup = if replaces_binary {
+                    let backup = self.app_dirs.share.join("deskimage").join("undo").join(&bin_name);
+                    match fs::create_dir_all(backup.parent().unwrap_or(Path::new("/"))).and_then(|_| snapshot_file(&exec_target, &backup)) {
+                        Ok(_) => Some(backup),
+                        Err(e) => {
+                            warn!("Couldn't keep the old binary for undo: {}", e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                self.undo_state = Some(UndoState {
+                    appname: appname.clone(),
+                    desktop_file,
+                    desktop_content,
+                    binary: exec_target.clone(),
+                    binary_backup,
+                });
+            }
+            if already_linked {
+                info!("Install target already resolves to the source, nothing to copy or link");
+            } else if self.install_mode == InstallMode::Symlink {
+                // Link mode: replace whatever is there (e.g. an earlier copy) with a symlink
+                match replace_with_symlink(appimage_path, &exec_target) {
+                    Ok(_) => info!(dest = %exec_target.display(), "Linked to source"),
+                    Err(e) => {
+                        warn!("Couldn't create symlink: {}", e);
+                        return Err(AppError::file("create symlink", &exec_target, e));
+                    }
+                }
+            } else if identical {
+                info!("Installed binary is unchanged from the source, skipping copy");
+            } else {
+                // A symlink left by link mode is replaced by the final rename, never written through.
+                // Copy on a background thread so the window stays responsive;
+                // the entry is finished in poll_copy_job once it's done
+                let (sender, receiver) = mpsc::channel();
+                let cancel_flag = Arc::new(AtomicBool::new(false));
+                let source = appimage_path.clone();
+                let dest = exec_target.clone();
+                let thread_cancel_flag = Arc::clone(&cancel_flag);
+                // Reuse the source's hash if it was already computed for display
+                let verify = self.settings.verify_copy.then(|| {
+                    self.checksum.clone().filter(|_| self.checksum_path.as_ref() == Some(&source))
+                });
+                std::thread::spawn(move || {
+                    let result = install_copy(&source, &dest, &sender, &thread_cancel_flag, verify);
+                    let _ = sender.send(CopyMessage::Finished(result));
+                });
+                
+                info!(dest = %exec_target.display(), "Copy started");
+                self.copy_progress = Some(0.0);
+                self.copy_job = Some(CopyJob {
+                    receiver,
+                    appname: appname.clone(),
+                    exec_target: exec_target.clone(),
+                    started: std::time::Instant::now(),
+                    cancel_flag,
+                });
+                self.update_status(StatusLevel::Info, format!("Copying to {}...", exec_target.display()));
+                return Ok(None);
+            }
+            
+            let copy_summary = if self.install_mode == InstallMode::InPlace {
+                "registered in place".to_string()
+            } else if self.install_mode == InstallMode::Symlink && !is_appimage {
+                "launched from its original location".to_string()
+            } else if self.install_mode == InstallMode::Symlink {
+                "linked to the original file".to_string()
+            } else {
+                "binary unchanged".to_string()
+            };
+            self.finish_desktop_entry(&appname, &exec_target, &copy_summary).map(Some)
+        } else {
+            Err(AppError::NoSource)
+        }
+    }
+    
+    // Install an executable without the window, for the command line. Goes through
+    // the same pipeline as the Create button, waiting for the copy to finish.
+    pub fn add_headless(&mut self, path: &Path, name: Option<String>, icon: Option<PathBuf>, categories: Option<String>, terminal: bool) -> Result<Outcome, AppError> {
+        let path = fs::canonicalize(path).map_err(|e| AppError::file("open", path, e))?;
+        self.appimage_path = Some(path.clone());
+        self.app_name = name.unwrap_or_else(|| self.default_app_name(&path));
+        self.load_existing_entry();
+        if let Some(icon) = icon {
+            self.icon_path = Some(icon);
+        }
+        if let Some(categories) = categories {
+            self.categories = split_list(&categories);
+        }
+        if terminal {
+            self.run_in_terminal = true;
+        }
+        self.install_headless()
+    }
+    
+    // Install from exported options, like Import options followed by Create.
+    // Flags given on the command line take precedence over the file.
+    pub fn add_from_headless(&mut self, options_path: &Path, name: Option<String>, icon: Option<PathBuf>, categories: Option<String>, terminal: bool) -> Result<Outcome, AppError> {
+        let mut options = InstallOptions::load(options_path)
+            .map_err(|e| AppError::InvalidOptions(options_path.to_path_buf(), e.to_string()))?;
+        options.source = fs::canonicalize(&options.source).map_err(|e| AppError::file("open", &options.source, e))?;
+        self.apply_options(options);
+        if let Some(name) = name {
+            self.app_name = name;
+        }
+        if let Some(icon) = icon {
+            self.icon_path = Some(icon);
+        }
+        if let Some(categories) = categories {
+            self.categories = split_list(&categories);
+        }
+        if terminal {
+            self.run_in_terminal = true;
+        }
+        self.install_headless()
+    }
+    
+    // Run Create for the filled-in form and wait for the copy to finish
+    fn install_headless(&mut self) -> Result<Outcome, AppError> {
+        let mut result = self.create_desktop_entry();
+        let smoke_test_job = if matches!(result, Ok(None)) { self.smoke_test_job.take() } else { None };
+        if let Some((_, receiver)) = smoke_test_job {
+            result = match receiver.recv() {
+                Ok(Ok(())) => {
+                    self.skip_smoke_test_once = true;
+                    self.create_desktop_entry()
+                }
+                Ok(Err(output)) => Err(AppError::SmokeTestFailed(output)),
+                Err(_) => Err(AppError::SmokeTestFailed("the test launch stopped unexpectedly".to_string())),
+            };
+        }
+        let copy_job = if matches!(result, Ok(None)) { self.copy_job.take() } else { None };
+        if let Some(job) = copy_job {
+            let copied = loop {
+                match job.receiver.recv() {
+                    Ok(CopyMessage::Progress(..) | CopyMessage::Verifying) => continue,
+                    Ok(CopyMessage::Finished(result)) => break result,
+                    Err(_) => break Err(std::io::Error::other("copy thread stopped unexpectedly")),
+                }
+            };
+            result = self.complete_copy_job(job, copied).map(Some);
+        }
+        self.save_settings();
+        result.and_then(|outcome| outcome.ok_or_else(|| std::io::Error::other("the copy didn't start").into()))
+    }
+    
+    // For scripts that add many entries and refresh the menu once at the end
+    pub fn set_skip_cache_update(&mut self, skip: bool) {
+        self.skip_cache_update = skip;
+    }
+    
+    pub fn remove_headless(&mut self, appname: &str) -> Result<Outcome, AppError> {
+        if !self.desktop_file_path(appname).is_some_and(|path| path.exists()) {
+            return Err(AppError::NoEntry(appname.to_string()));
+        }
+        self.remove_desktop_entry(appname)
+    }
+    
+    pub fn installed_entries(&self) -> &[InstalledEntry] {
+        &self.installed_entries
+    }
+    
+    // Remove every entry DeskImage created, for `deskimage purge`
+    pub fn purge_headless(&mut self) -> Result<Outcome, AppError> {
+        self.remove_all_entries()
+    }
+    
+    // Installed entries DeskImage itself created: recorded in its history or
+    // marked with X-DeskImage-Installed. Others that merely point into the
+    // install directory are left alone.
+    pub fn purgeable_entries(&self) -> Vec<InstalledEntry> {
+        self.installed_entries.iter()
+            .filter(|entry| entry.added_on.is_some() || entry.desktop.deskimage_installed)
+            .cloned()
+            .collect()
+    }
+    
+    // Remove each purgeable entry with its binary and summarize the result
+    fn remove_all_entries(&mut self) -> Result<Outcome, AppError> {
+        let entries = self.purgeable_entries();
+        let skipped = self.installed_entries.len() - entries.len();
+        // Each removal gets its own uninstall span inside this one
+        let _span = tracing::info_span!("purge", count = entries.len()).entered();
+        info!("Removing {} entries, skipping {} not created by DeskImage", entries.len(), skipped);
+        
+        let mut removed = 0;
+        let mut failed = Vec::new();
+        for entry in &entries {
+            let Some(appname) = entry.path.file_stem() else {
+                continue;
+            };
+            match self.remove_desktop_entry(&appname.to_string_lossy()) {
+                Ok(Outcome::Success(_)) => removed += 1,
+                Ok(Outcome::Warning(_)) => {}
+                Err(_) => failed.push(entry.desktop.name.clone()),
+            }
+        }
+        
+        if !failed.is_empty() {
+            return Err(AppError::PurgeIncomplete { removed, skipped, failed });
+        }
+        let skipped_note = if skipped > 0 {
+            format!(", skipped {} not created by DeskImage", skipped)
+        } else {
+            String::new()
+        };
+        Ok(Outcome::Success(format!("Removed {} entries{}", removed, skipped_note)))
+    }
+    
+    // Show how an operation went in the status banner
+    fn report(&mut self, result: Result<Outcome, AppError>) {
+        let (level, message) = match result {
+            Ok(Outcome::Success(message)) => (StatusLevel::Success, message),
+            Ok(Outcome::Warning(message)) => (StatusLevel::Warning, message),
+            // Stopping a copy on purpose isn't a failure
+            Err(e @ AppError::Cancelled(_)) => (StatusLevel::Warning, capitalize(&e.to_string())),
+            Err(e) => (StatusLevel::Error, capitalize(&e.to_string())),
+        };
+        self.update_status(level, message);
+    }
+    
+    // Check on a running background copy, finishing the entry once it completes
+    fn poll_copy_job(&mut self, ctx: &egui::Context) {
+        let Some(job) = &self.copy_job else {
+            return;
+        };
+        
+        let mut finished = None;
+        let mut verifying = false;
+        loop {
+            match job.receiver.try_recv() {
+                Ok(CopyMessage::Progress(copied, total)) => {
+                    self.copy_progress = Some(if total == 0 { 1.0 } else { copied as f32 / total as f32 });
+                }
+                Ok(CopyMessage::Verifying) => verifying = true,
+                Ok(CopyMessage::Finished(result)) => {
+                    finished = Some(result);
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = Some(Err(std::io::Error::other("copy thread stopped unexpectedly")));
+                    break;
+                }
+            }
+        }
+        
+        if verifying {
+            let dest = job.exec_target.display().to_string();
+            self.update_status(StatusLevel::Info, format!("Verifying {}...", dest));
+        }
+        
+        let Some(result) = finished else {
+            // Keep repainting so the progress bar moves
+            ctx.request_repaint();
+            return;
+        };
+        
+        let Some(job) = self.copy_job.take() else {
+            return;
+        };
+        let result = self.complete_copy_job(job, result);
+        self.finish_create(ctx, result);
+    }
+    
+    // Report how a Create ended, wherever it finished
+    fn finish_create(&mut self, ctx: &egui::Context, result: Result<Outcome, AppError>) {
+        self.record_batch_result(&result);
+        self.report(result);
+        self.save_settings();
+        self.signal_completion(ctx, &self.completion_subject());
+        self.launch_if_requested();
+    }
+    
+    // Pick up the strict-mode test launch and continue or fail the Create
+    fn poll_smoke_test(&mut self, ctx: &egui::Context) {
+        let Some((path, receiver)) = &self.smoke_test_job else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Err("the test launch stopped unexpectedly".to_string()),
+        };
+        let path = path.clone();
+        self.smoke_test_job = None;
+        
+        match result {
+            // Another file may have been picked meanwhile; it wasn't tested
+            Ok(()) if self.appimage_path.as_ref() != Some(&path) => {
+                self.update_status(StatusLevel::Warning, "The selected file changed during the test launch, nothing was created".to_string());
+            }
+            Ok(()) => {
+                info!("Test launch of {} passed", path.display());
+                self.skip_smoke_test_once = true;
+                if let Some(result) = self.create_desktop_entry().transpose() {
+                    self.finish_create(ctx, result);
+                }
+            }
+            Err(output) => {
+                warn!("Smoke test failed: {}", output);
+                self.smoke_test_failed = true;
+                self.finish_create(ctx, Err(AppError::SmokeTestFailed(output)));
+            }
+        }
+    }
+    
+    // Start the binary of the entry that was just created, for "Install and Launch".
+    // Nothing happens if the create failed.
+    fn launch_if_requested(&mut self) {
+        if !std::mem::take(&mut self.launch_after_create) {
+            return;
+        }
+        let Some((_, exec_target)) = self.last_created.clone() else {
+            return;
+        };
+        
+        info!("Launching {}", exec_target.display());
+        let spawned = Command::new(&exec_target)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        match spawned {
+            // Reap it whenever it exits so it doesn't linger as a zombie
+            Ok(mut child) => {
+                std::thread::spawn(move || child.wait());
+            }
+            Err(e) => {
+                warn!("Couldn't launch {}: {}", exec_target.display(), e);
+                self.update_status(StatusLevel::Error, format!("The entry was created, but {} failed to start: {}", exec_target.display(), e));
+            }
+        }
+    }
+    
+    // Ask the AppImage runtime for its update information. Type 1 images don't
+    // support the flag, so they aren't run at all.
+    fn check_for_update(&mut self) {
+        let Some(path) = self.appimage_path.clone() else {
+            return;
+        };
+        let _span = tracing::info_span!("update", app = %path.display()).entered();
+        if appimage::appimage_type(&path).ok().flatten() == Some(1) {
+            self.update_status(StatusLevel::Warning, "Type 1 AppImages have no update information".to_string());
+            return;
+        }
+        
+        match Command::new(&path).arg("--appimage-updateinformation").output() {
+            Ok(output) if output.status.success() => {
+                let info = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                info!("Update information of {}: {:?}", path.display(), info);
+                if info.is_empty() {
+                    self.update_info = None;
+                    self.update_status(StatusLevel::Info, format!("{} has no update information, it can't be updated in place", path.display()));
+                } else if self.update_tool_available {
+                    self.update_status(StatusLevel::Info, format!("Update info: {}. Use \"Update installed copy\" to update in place.", info));
+                    self.update_info = Some(info);
+                } else {
+                    self.update_status(StatusLevel::Info, format!("Update info: {}. Install appimageupdatetool to update in place.", info));
+                    self.update_info = Some(info);
+                }
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                warn!("--appimage-updateinformation failed ({}): {}", output.status, stderr);
+                self.update_status(StatusLevel::Warning, format!("Couldn't read update information ({}): {}", output.status, stderr));
+            }
+            Err(e) => self.update_status(StatusLevel::Error, format!("Couldn't run {}: {}", path.display(), e)),
+        }
+    }
+    
+    // Update the installed copy (or the selected file if there's none) in place
+    // with appimageupdatetool, on a background thread since it downloads
+    fn start_update(&mut self, ctx: &egui::Context) {
+        let installed = self.entry_id()
+            .and_then(|appname| self.desktop_file_path(&appname))
+            .and_then(|path| DesktopEntry::from_file(&path).ok())
+            .map(|existing| PathBuf::from(existing.exec))
+            .filter(|exec| exec.exists());
+        let Some(target) = installed.or_else(|| self.appimage_path.clone()) else {
+            return;
+        };
+        
+        let span = tracing::info_span!("update", app = %target.display());
+        let _entered = span.enter();
+        let (sender, receiver) = mpsc::channel();
+        let thread_target = target.clone();
+        let thread_span = span.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _span = thread_span.entered();
+            let result = Command::new("appimageupdatetool").arg("--overwrite").arg(&thread_target).output();
+            let _ = sender.send(result);
+            ctx.request_repaint();
+        });
+        info!("Updating {} with appimageupdatetool", target.display());
+        self.update_status(StatusLevel::Info, format!("Updating {}...", target.display()));
+        self.update_job = Some((target, receiver));
+    }
+    
+    fn poll_update_job(&mut self) {
+        let Some((target, receiver)) = &self.update_job else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(std::io::Error::other("update thread stopped unexpectedly")),
+        };
+        let target = target.clone();
+        self.update_job = None;
+        let _span = tracing::info_span!("update", app = %target.display()).entered();
+        
+        match result {
+            Ok(output) if output.status.success() => {
+                info!("appimageupdatetool finished for {}", target.display());
+                self.update_status(StatusLevel::Success, format!("Updated {}", target.display()));
+            }
+            Ok(output) => {
+                // The last line usually says what went wrong
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("no output");
+                self.update_status(StatusLevel::Error, format!("appimageupdatetool failed for {} ({}): {}", target.display(), output.status, reason.trim()));
+            }
+            Err(e) => self.update_status(StatusLevel::Error, format!("Couldn't run appimageupdatetool: {}", e)),
+        }
+    }
+    
+    // Launch the selected app on a thread to read its window class
+    fn start_wm_class_detection(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.appimage_path.clone() else {
+            return;
+        };
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(detect_wm_class(&path));
+            ctx.request_repaint();
+        });
+        self.update_status(StatusLevel::Info, "Detecting the window class...".to_string());
+        self.wm_class_job = Some(receiver);
+    }
+    
+    fn poll_wm_class_job(&mut self) {
+        let Some(receiver) = &self.wm_class_job else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("detection stopped unexpectedly".to_string()),
+        };
+        self.wm_class_job = None;
+        
+        match result {
+            Ok(wm_class) => {
+                self.update_status(StatusLevel::Info, format!("Detected window class: {}", wm_class));
+                self.startup_wm_class = wm_class;
+            }
+            Err(e) => {
+                warn!("Couldn't detect window class: {}", e);
+                self.update_status(StatusLevel::Warning, format!("Couldn't detect the window class: {}", e));
+            }
+        }
+    }
+    
+    // Hash the selected file whenever it changes, and pick up the result once ready
+    fn poll_checksum(&mut self, ctx: &egui::Context) {
+        if self.checksum_path != self.appimage_path {
+            self.checksum_path = self.appimage_path.clone();
+            self.checksum = None;
+            // A multi-GB file would otherwise keep being read to the end for nothing
+            self.checksum_cancel_flag.store(true, Ordering::Relaxed);
+            self.checksum_cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag = Arc::clone(&self.checksum_cancel_flag);
+            self.checksum_receiver = self.appimage_path.clone().map(|path| {
+                let (sender, receiver) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let _ = sender.send(sha256_file(&path, &cancel_flag));
+                });
+                receiver
+            });
+        }
+        
+        let Some(receiver) = &self.checksum_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(Ok(checksum)) => {
+                debug!("SHA-256: {}", checksum);
+                self.checksum = Some(checksum);
+                self.checksum_receiver = None;
+            }
+            Ok(Err(e)) => {
+                warn!("Couldn't compute checksum: {}", e);
+                self.checksum_receiver = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            Err(mpsc::TryRecvError::Disconnected) => self.checksum_receiver = None,
+        }
+    }
+    
+    // Finish the entry, or report the failure, once a background copy is done
+    fn complete_copy_job(&mut self, job: CopyJob, result: Result<u64, std::io::Error>) -> Result<Outcome, AppError> {
+        self.copy_progress = None;
+        let _span = tracing::info_span!("install", app = %job.appname).entered();
+        
+        match result {
+            Ok(bytes) => {
+                let elapsed = job.started.elapsed();
+                let summary = format!("copied {} in {}", format_size(bytes), format_duration(elapsed));
+                info!(bytes, elapsed_ms = elapsed.as_millis() as u64, dest = %job.exec_target.display(), "Copy finished");
+                self.finish_desktop_entry(&job.appname, &job.exec_target, &summary)
+            }
+            Err(e) => {
+                // Nothing was replaced, so there is nothing to undo
+                if let Some(undo) = self.undo_state.take() {
+                    discard_undo_backup(&undo);
+                }
+                if job.cancel_flag.load(Ordering::Relaxed) {
+                    info!(dest = %job.exec_target.display(), "Copy cancelled");
+                    Err(AppError::Cancelled(job.exec_target))
+                } else {
+                    warn!("Couldn't copy file: {}", e);
+                    Err(AppError::file("copy the file to", &job.exec_target, e))
+                }
+            }
+        }
+    }
+    
+    // Ask the copy thread to stop; poll_copy_job reports the outcome.
+    // A folder install stops after the current file too.
+    fn cancel_copy(&mut self) {
+        let Some(job) = &self.copy_job else {
+            return;
+        };
+        info!("Cancelling copy to {}", job.exec_target.display());
+        job.cancel_flag.store(true, Ordering::Relaxed);
+        if let Some(batch) = &mut self.batch {
+            batch.queue.clear();
+        }
+        self.update_status(StatusLevel::Info, "Cancelling...".to_string());
+    }
+    
+    // Put back the desktop file and binary the last Create overwrote
+    fn undo_last_overwrite(&mut self) {
+        let Some(undo) = self.undo_state.take() else {
+            return;
+        };
+        info!("Restoring previous entry for {}", undo.appname);
+        if let Err(e) = desktop::write_desktop_file(&undo.desktop_file, &undo.desktop_content) {
+            warn!("Couldn't restore desktop file: {}", e);
+            self.update_status(StatusLevel::Error, format!("Couldn't restore {}: {}", undo.desktop_file.display(), e));
+            return;
+        }
+        if let Some(backup) = &undo.binary_backup {
+            if fs::symlink_metadata(&undo.binary).is_ok() {
+                if let Err(e) = fs::remove_file(&undo.binary) {
+                    warn!("Couldn't remove the new binary: {}", e);
+                }
+            }
+            if let Err(e) = move_file(backup, &undo.binary) {
+                warn!("Couldn't restore binary: {}", e);
+                self.update_status(StatusLevel::Error, format!("Couldn't restore {}: {}", undo.binary.display(), e));
+                return;
+            }
+        }
+        
+        self.refresh_menu_caches(&self.app_dirs.applications, &self.app_dirs.icons.join("hicolor"));
+        self.refresh_installed_entries();
+        self.last_created = None;
+        self.update_status(StatusLevel::Success, format!("Restored the previous entry for {}", undo.appname));
+    }
+    
+    // Everything after the binary is in place: permissions, the desktop file,
+    // autostart and cache refreshes
+    fn finish_desktop_entry(&mut self, appname: &str, exec_target: &Path, copy_summary: &str) -> Result<Outcome, AppError> {
+        // Set executable permissions on the destination file
+        self.make_executable(exec_target).map_err(|e| {
+            warn!("Couldn't set permissions: {}", e);
+            match e {
+                AppError::Io(e) => AppError::file("set permissions on", exec_target, e),
+                e => e,
+            }
+        })?;
+
+        let applications_dir = self.app_dirs.applications.clone();
+        debug!("Applications directory: {}", applications_dir.display());
+        
+        // Ensure the applications directory exists
+        fs::create_dir_all(&applications_dir).map_err(|e| {
+            warn!("Couldn't create applications directory: {}", e);
+            AppError::file("create directory", &applications_dir, e)
+        })?;
+        
+        let desktop_file_path = self.desktop_file_path(appname)
+            .unwrap_or_else(|| applications_dir.join(desktop_file_name(appname)));
+        debug!("Desktop file path: {}", desktop_file_path.display());
+        
+        // Check if the desktop entry already exists before we start
+        let desktop_existed = desktop_file_path.exists();
+        debug!("Desktop file existed before: {}", desktop_existed);
+        
+        let existing = DesktopEntry::from_file(&desktop_file_path).ok();
+        let previous_version = existing.as_ref()
+            .map(|existing| existing.version.clone())
+            .filter(|version| !version.is_empty());
+        
+        // Preserve the custom icon of an existing entry if no new one is selected
+        let existing_icon = existing
+            .map(|existing| existing.icon)
+            .filter(|icon| !icon.is_empty());
+        
+        // Handle custom icon if selected, otherwise a new entry gets the AppImage's own icon
+        let chosen_icon = self.icon_path.clone()
+            .filter(|icon_path| icon_path.exists())
+            .or_else(|| self.bundled_icons.first().cloned().filter(|_| existing_icon.is_none()));
+        let mut icon_error = None;
+        let icon_value = match chosen_icon {
+            Some(icon_path) => {
+                match self.install_icon_themed(&icon_path, appname) {
+                    Ok(icon_name) => icon_name,
+                    Err(e) => {
+                        warn!("Couldn't install icon into the hicolor theme: {}", e);
+                        icon_error = Some(e);
+                        // Continue with the original path as fallback
+                        icon_path.to_string_lossy().to_string()
+                    }
+                }
+            }
+            // Icon doesn't exist or none was selected, keep the existing one
+            None => existing_icon.unwrap_or_else(|| "application-x-executable".to_string()),
+        };
+        
+        // Create desktop entry content with preserved or new icon value
+        let entry = self.desktop_entry_for(appname, exec_target, icon_value);
+        let desktop_content = entry.to_string();
+        
+        // Write the desktop file
+        entry.write_to(&desktop_file_path).map_err(|e| {
+            warn!("Couldn't write desktop file: {}", e);
+            AppError::file("write", &desktop_file_path, e)
+        })?;
+        info!(path = %desktop_file_path.display(), "Successfully wrote desktop file");
+        
+        // Keep the autostart copy in sync with the checkbox
+        let autostart_error = self.sync_autostart(appname, &desktop_content, self.autostart).err();
+        if let Some(e) = &autostart_error {
+            warn!("Couldn't update autostart entry: {}", e);
+        }
+        
+        // Attempt to update the desktop database and icon cache to make it immediately visible,
+        // unless the user defers that to a single manual refresh later
+        let database_updated = if self.skip_cache_update {
+            info!("Skipping desktop database and icon cache update");
+            false
+        } else {
+            self.refresh_menu_caches(&applications_dir, &self.app_dirs.icons.join("hicolor"))
+        };
+        // Menus pick up the entry right away once the database is rebuilt
+        let visibility = if database_updated {
+            "visible in menu"
+        } else {
+            "written, but may need a re-login to show up in the menu"
+        };
+
+        // Make this app the default for its file types
+        let mime_types: Vec<&str> = entry.mime_types.iter().map(String::as_str).collect();
+        let mime_failures = self.register_mime_associations(appname, &mime_types);
+        
+        self.record_action("install", &entry);
+        self.refresh_installed_entries();
+        self.refresh_installed_state();
+        
+        // Let desktop-file-validate point out anything menus might reject
+        let (validation_issues, validation_note) = match self.validate_desktop_file(&desktop_file_path) {
+            Ok(issues) => (issues, ""),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("desktop-file-validate not found, skipping validation");
+                (Vec::new(), ", validation skipped: desktop-file-validate isn't installed")
+            }
+            Err(e) => {
+                warn!("Couldn't run desktop-file-validate: {}", e);
+                (Vec::new(), ", validation skipped")
+            }
+        };
+
+        // Other entries with the same Name= make the menu ambiguous
+        let name_collisions = find_name_collisions(&desktop_file_path, &entry.name);
+        for collision in &name_collisions {
+            warn!("Name collision: {}", collision);
+        }
+
+        // Verify the desktop entry was written and reads back as a usable entry
+        let verified = fs::read_to_string(&desktop_file_path).and_then(|content| {
+            let missing = desktop::missing_required_keys(&content);
+            if missing.is_empty() {
+                Ok(())
+            } else {
+                Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("missing {}", missing.join(", "))))
+            }
+        });
+        match verified {
+            Ok(_) => {
+                info!("Successfully verified desktop entry");
+                self.last_created = Some((desktop_file_path.clone(), exec_target.to_path_buf()));
+                let outcome = if let Some(e) = icon_error {
+                    Outcome::Warning(format!("Desktop entry written to {}, but the icon couldn't be installed: {}", desktop_file_path.display(), e))
+                } else if let Some(e) = autostart_error {
+                    Outcome::Warning(format!("Desktop entry written to {}, but autostart couldn't be updated: {}", desktop_file_path.display(), e))
+                } else if !mime_failures.is_empty() {
+                    Outcome::Warning(format!("Desktop entry written to {}, but some file associations failed: {}",
+                        desktop_file_path.display(), mime_failures.join(", ")))
+                } else if !validation_issues.is_empty() {
+                    Outcome::Warning(format!("Desktop entry written to {}, but desktop-file-validate reported:\n{}",
+                        desktop_file_path.display(), validation_issues.join("\n")))
+                } else if !name_collisions.is_empty() {
+                    Outcome::Warning(format!("Desktop entry written to {}, but other entries are also named \"{}\":\n{}",
+                        desktop_file_path.display(), entry.name, name_collisions.join("\n")))
+                } else if desktop_existed {
+                    // Mention a version change when re-installing a newer (or older) release
+                    let version_note = match previous_version {
+                        Some(previous) if !entry.version.is_empty() && previous != entry.version => {
+                            format!(", updated {} → {}", previous, entry.version)
+                        }
+                        _ => String::new(),
+                    };
+                    Outcome::Success(format!("Desktop entry updated at: {} ({}{}{}), {}", desktop_file_path.display(), copy_summary, version_note, validation_note, visibility))
+                } else {
+                    Outcome::Success(format!("Desktop entry created at: {} ({}{}), {}", desktop_file_path.display(), copy_summary, validation_note, visibility))
+                };
+                Ok(outcome)
+            },
+            Err(e) => {
+                warn!("Failed to verify desktop entry: {}", e);
+                Err(AppError::Unverified(e))
+            }
+        }
+    }
+
+    // Set the entry as the default application for each MIME type through
+    // xdg-mime, returning a description of every type that failed
+    fn register_mime_associations(&self, appname: &str, types: &[&str]) -> Vec<String> {
+        let desktop_file = desktop_file_name(appname);
+        let mut failures = Vec::new();
+        for mime_type in types {
+            match Command::new("xdg-mime").args(["default", &desktop_file, mime_type]).status() {
+                Ok(status) if status.success() => info!("Registered {} for {}", desktop_file, mime_type),
+                Ok(status) => {
+                    warn!("xdg-mime failed for {}: {}", mime_type, status);
+                    failures.push(format!("{} ({})", mime_type, status));
+                }
+                Err(e) => {
+                    warn!("Couldn't run xdg-mime: {}", e);
+                    failures.push(format!("{} ({})", mime_type, e));
+                }
+            }
+        }
+        failures
+    }
+    
+    // Run desktop-file-validate on a written entry and collect what it reports.
+    // Fails with NotFound when the tool isn't installed.
+    fn validate_desktop_file(&self, path: &Path) -> Result<Vec<String>, std::io::Error> {
+        let output = Command::new("desktop-file-validate").arg(path).output()?;
+        let issues: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        for issue in &issues {
+            warn!("desktop-file-validate: {}", issue);
+        }
+        Ok(issues)
+    }
+    
+    // Install an icon into the user's hicolor theme as <appname>.png/.svg and
+    // return the themed icon name to put in Icon=
+    fn install_icon_themed(&self, icon: &Path, appname: &str) -> Result<String, std::io::Error> {
+        let hicolor_dir = self.app_dirs.icons.join("hicolor");
+        let icon_name = safe_filename(appname);
+        
+        if icon.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+            let apps_dir = hicolor_dir.join("scalable/apps");
+            fs::create_dir_all(&apps_dir)?;
+            fs::copy(icon, apps_dir.join(format!("{}.svg", icon_name)))?;
+        } else {
+            // Themes only look up PNGs in the size directories index.theme lists, so the
+            // image is scaled down or padded to a square standard size; re-encoding also
+            // turns JPGs into PNGs
+            let image = image::open(icon).map_err(std::io::Error::other)?;
+            let size = hicolor_size(image.width().max(image.height()));
+            let apps_dir = hicolor_dir.join(format!("{}x{}/apps", size, size));
+            fs::create_dir_all(&apps_dir)?;
+            fit_to_square(&image, size)
+                .save_with_format(apps_dir.join(format!("{}.png", icon_name)), image::ImageFormat::Png)
+                .map_err(std::io::Error::other)?;
+        }
+        
+        // Icon= only works if a theme lookup of the name finds a file, so check
+        // before handing the name out instead of the path
+        if !self.themed_icon_paths(&icon_name).any(|path| path.is_file()) {
+            return Err(std::io::Error::new(std::io::ErrorKind::NotFound,
+                format!("{} isn't found in the hicolor theme under {}", icon_name, hicolor_dir.display())));
+        }
+        
+        info!(icon = %icon.display(), name = %icon_name, "Installed icon into hicolor theme");
+        Ok(icon_name)
+    }
+    
+    // Where a theme lookup of a name finds the icons install_icon_themed writes
+    fn themed_icon_paths(&self, icon_name: &str) -> impl Iterator<Item = PathBuf> {
+        let hicolor_dir = self.app_dirs.icons.join("hicolor");
+        let scalable = hicolor_dir.join(format!("scalable/apps/{}.svg", icon_name));
+        let icon_name = icon_name.to_string();
+        HICOLOR_SIZES.iter()
+            .map(move |size| hicolor_dir.join(format!("{}x{}/apps/{}.png", size, size, icon_name)))
+            .chain(std::iter::once(scalable))
+    }
+    
+    // Delete the icons install_icon_themed put into the hicolor theme for an entry.
+    // Only a themed name of our own is touched; paths and stock icons are left alone.
+    fn remove_themed_icons(&self, appname: &str, icon_value: &str) {
+        let icon_name = safe_filename(appname);
+        if icon_value != icon_name {
+            return;
+        }
+        for icon in self.themed_icon_paths(&icon_name) {
+            match fs::remove_file(&icon) {
+                Ok(_) => info!("Removed icon {}", icon.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!("Couldn't remove icon {}: {}", icon.display(), e),
+            }
+        }
+    }
+    
+    // The entry the form describes, for a given name, install location and icon
+    fn desktop_entry_for(&self, appname: &str, exec_target: &Path, icon: String) -> DesktopEntry {
+        // With an app ID the file is named after the ID, Name= stays the friendly name
+        let name = if self.app_id.trim().is_empty() {
+            appname.to_string()
+        } else {
+            self.effective_app_name().unwrap_or_else(|| appname.to_string())
+        };
+        DesktopEntry {
+            name,
+            generic_name: self.generic_name.clone(),
+            exec: exec_target.to_string_lossy().to_string(),
+            exec_args: self.exec_args.trim().to_string(),
+            icon,
+            categories: self.categories.clone(),
+            keywords: split_list(&self.keywords),
+            mime_types: split_list(&self.mime_types),
+            comment: self.comment.clone(),
+            terminal: self.run_in_terminal,
+            wm_class: self.startup_wm_class.clone(),
+            version: self.version.clone().unwrap_or_default(),
+            zsync_url: self.zsync_url.clone().filter(|_| self.store_zsync_url).unwrap_or_default(),
+            // Half-filled rows are skipped; ids may only use letters, digits and '-'
+            actions: self.actions.iter()
+                .map(|action| DesktopAction {
+                    id: action.id.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect(),
+                    name: action.name.trim().to_string(),
+                    exec: action.exec.trim().to_string(),
+                })
+                .filter(|action| !action.id.is_empty() && !action.name.is_empty() && !action.exec.is_empty())
+                .collect(),
+            deskimage_installed: true,
+        }
+    }
+    
+    // Install for all users: the binary to /usr/local/bin, the entry to
+    // /usr/share/applications and a custom icon to /usr/share/pixmaps, all in
+    // one elevated step so there's a single password prompt
+    fn install_system_wide(&mut self, appname: &str, bin_name: &str, source: &Path) -> Result<Outcome, AppError> {
+        use std::io::Write;
+        
+        let exec_target = Path::new(SYSTEM_BIN_DIR).join(bin_name);
+        let desktop_file_path = Path::new(SYSTEM_APPLICATIONS_DIR).join(desktop_file_name(appname));
+        
+        // A themed name resolves through /usr/share/pixmaps for every user. It follows
+        // the file name rule, so nothing in the name can point root's install elsewhere.
+        let icon_name = safe_filename(appname);
+        let icon = self.icon_path.clone().filter(|icon| icon.exists());
+        let icon_dest = icon.as_ref().map(|icon| {
+            let extension = icon.extension().unwrap_or_default().to_string_lossy();
+            Path::new(SYSTEM_PIXMAPS_DIR).join(format!("{}.{}", icon_name, extension))
+        });
+        let icon_value = if icon.is_some() {
+            icon_name.clone()
+        } else {
+            DesktopEntry::from_file(&desktop_file_path)
+                .ok()
+                .map(|existing| existing.icon)
+                .filter(|icon| !icon.is_empty())
+                .unwrap_or_else(|| "application-x-executable".to_string())
+        };
+        
+        let entry = self.desktop_entry_for(appname, &exec_target, icon_value);
+        // A fresh private file: a predictable name in /tmp could be a symlink planted by someone else
+        let mut staged = tempfile::Builder::new().prefix("deskimage-").suffix(".desktop").tempfile()?;
+        staged.write_all(entry.to_string().as_bytes()).map_err(|e| AppError::file("write", staged.path(), e))?;
+        let staged_entry = staged.path().to_path_buf();
+        
+        // Paths are passed as positional arguments, never spliced into the script
+        const SCRIPT: &str = r#"install -Dm755 "$1" "$2" && install -Dm644 "$3" "$4" && { [ -z "$5" ] || install -Dm644 "$5" "$6"; } && { update-desktop-database "$7" 2>/dev/null || true; }"#;
+        let icon_source = icon.as_ref().map(|icon| icon.as_os_str()).unwrap_or_default();
+        let icon_target = icon_dest.as_ref().map(|icon| icon.as_os_str()).unwrap_or_default();
+        info!(dest = %exec_target.display(), entry = %desktop_file_path.display(), "Installing system-wide");
+        let status = run_elevated(&[
+            OsStr::new("sh"), OsStr::new("-c"), OsStr::new(SCRIPT), OsStr::new("sh"),
+            source.as_os_str(), exec_target.as_os_str(),
+            staged_entry.as_os_str(), desktop_file_path.as_os_str(),
+            icon_source, icon_target,
+            OsStr::new(SYSTEM_APPLICATIONS_DIR),
+        ]);
+        drop(staged);
+        
+        if let Err(e) = status {
+            warn!("System-wide install failed: {}", e);
+            return Err(e);
+        }
+        self.record_action("install", &entry);
+        self.last_created = Some((desktop_file_path.clone(), exec_target.clone()));
+        Ok(Outcome::Success(format!("Installed for all users at: {}", desktop_file_path.display())))
+    }
+    
+    // Scan the applications directory for entries whose Exec= points into the install
+    // dir, or at a file that was registered in place
+    fn list_installed_entries(&self) -> Vec<InstalledEntry> {
+        let Ok(dir) = fs::read_dir(&self.app_dirs.applications) else {
+            return Vec::new();
+        };
+        
+        // Latest install of the same binary, including ones from earlier sessions
+        let records = history::load();
+        let mut entries: Vec<InstalledEntry> = dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .filter_map(|path| {
+                let desktop = DesktopEntry::from_file(&path).ok()?;
+                if desktop.exec.is_empty() {
+                    return None;
+                }
+                let exec = Path::new(&desktop.exec);
+                let added_on = records.iter()
+                    .rev()
+                    .find(|record| record.action == "install" && record.dest == exec)
+                    .map(|record| record.timestamp);
+                // An in-place entry's Exec= is the source itself, only the history knows it's ours
+                if !exec.starts_with(&self.install_dir) && added_on.is_none() {
+                    return None;
+                }
+                let autostart = path.file_stem()
+                    .and_then(|appname| self.autostart_file_path(&appname.to_string_lossy()))
+                    .is_some_and(|autostart_path| autostart_path.exists());
+                Some(InstalledEntry { path, desktop, added_on, autostart })
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.desktop.name.to_lowercase());
+        entries
+    }
+    
+    fn refresh_installed_entries(&mut self) {
+        self.installed_entries = self.list_installed_entries();
+        // Collisions and existing icons may have changed
+        self.file_preview = None;
+    }
+    
+    // Start watching the applications directory; the list keeps working without it
+    fn watch_applications_dir(&mut self, ctx: &egui::Context) {
+        use notify::Watcher;
+        
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if event.is_ok_and(|event| !event.kind.is_access()) {
+                let _ = sender.send(());
+                ctx.request_repaint();
+            }
+        });
+        let result = watcher.and_then(|mut watcher| {
+            watcher.watch(&self.app_dirs.applications, notify::RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        match result {
+            Ok(watcher) => {
+                info!("Watching {} for changes", self.app_dirs.applications.display());
+                self.applications_watch = Some(ApplicationsWatch {
+                    _watcher: watcher,
+                    receiver,
+                    changed_at: None,
+                });
+            }
+            Err(e) => warn!("Couldn't watch {}: {}", self.app_dirs.applications.display(), e),
+        }
+    }
+    
+    // Rescan the installed entries once changes to the applications directory have settled
+    fn poll_applications_watch(&mut self, ctx: &egui::Context) {
+        let Some(watch) = &mut self.applications_watch else {
+            return;
+        };
+        if watch.receiver.try_iter().count() > 0 {
+            watch.changed_at = Some(std::time::Instant::now());
+        }
+        let Some(changed_at) = watch.changed_at else {
+            return;
+        };
+        
+        let quiet_for = changed_at.elapsed();
+        if quiet_for < WATCH_DEBOUNCE {
+            ctx.request_repaint_after(WATCH_DEBOUNCE - quiet_for);
+            return;
+        }
+        watch.changed_at = None;
+        debug!("Applications directory changed, refreshing installed entries");
+        self.refresh_installed_entries();
+    }
+    
+    // Append an install/remove to the history log; failing to log never fails the action
+    fn record_action(&self, action: &str, entry: &DesktopEntry) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let record = HistoryRecord {
+            timestamp,
+            action: action.to_string(),
+            appname: entry.name.clone(),
+            source: if action == "install" { self.appimage_path.clone() } else { None },
+            dest: PathBuf::from(&entry.exec),
+        };
+        if let Err(e) = history::append(record) {
+            warn!("Couldn't write history: {}", e);
+        }
+    }
+    
+    // Load an installed entry back into the form so it can be edited and re-created
+    fn edit_installed_entry(&mut self, entry: &InstalledEntry) {
+        let exec = PathBuf::from(&entry.desktop.exec);
+        self.update_info = appimage::read_update_info(&exec).ok().flatten();
+        self.appimage_path = Some(exec);
+        let stem = entry.path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| entry.desktop.name.clone());
+        // A reverse-DNS file name is an app ID, the name then comes from Name=
+        if is_valid_app_id(&stem) && stem != entry.desktop.name {
+            self.app_id = stem;
+            self.app_name = entry.desktop.name.clone();
+        } else {
+            self.app_id.clear();
+            self.app_name = stem;
+        }
+        // Keep the existing Icon= unless a new one is picked
+        self.icon_path = None;
+        self.icon_texture = None;
+        self.clear_bundled();
+        self.load_existing_entry();
+        self.update_status(StatusLevel::Info, format!("Editing {}", entry.desktop.name));
+    }
+    
+    // List of installed entries with per-row Edit and Remove buttons
+    fn show_installed_entries(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Installed apps ({})", self.installed_entries.len())).size(16.0).strong());
+            if ui.small_button("Refresh").clicked() {
+                self.refresh_installed_entries();
+            }
+            if ui.add_enabled(!self.work_in_progress(), egui::Button::new("Remove all…").small())
+                .on_hover_text("Remove every entry DeskImage created, with its installed file")
+                .clicked() {
+                self.purge_prompt = true;
+            }
+        });
+        
+        if self.installed_entries.is_empty() {
+            ui.label(RichText::new("No AppImages installed by DeskImage yet").size(13.0).color(Color32::from_rgb(150, 150, 170)));
+            return;
+        }
+        
+        ui.add(egui::TextEdit::singleline(&mut self.installed_filter)
+            .hint_text("Filter by name or path")
+            .desired_width(300.0));
+        let filter = self.installed_filter.trim().to_lowercase();
+        
+        let mut edit = None;
+        let mut remove = None;
+        let mut toggle_autostart = None;
+        egui::ScrollArea::vertical()
+            .id_salt("installed_entries")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                let mut shown = 0;
+                for entry in &self.installed_entries {
+                    if !filter.is_empty()
+                        && !entry.desktop.name.to_lowercase().contains(&filter)
+                        && !entry.desktop.exec.to_lowercase().contains(&filter) {
+                        continue;
+                    }
+                    shown += 1;
+                    egui::Frame::new()
+                        .fill(Color32::from_rgb(30, 30, 40))
+                        .corner_radius(6)
+                        .inner_margin(8.0)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.vertical(|ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new(&entry.desktop.name).size(14.0).strong());
+                                        if let Some(added_on) = entry.added_on {
+                                            ui.label(RichText::new(format!("added on {}", format_date(added_on))).size(11.0).color(Color32::from_rgb(150, 150, 170)));
+                                        }
+                                    });
+                                    ui.label(RichText::new(truncate_middle(&entry.desktop.exec, MAX_PATH_CHARS)).monospace().size(11.0).color(Color32::from_rgb(150, 150, 170)))
+                                        .on_hover_text(&entry.desktop.exec);
+                                });
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.button("Remove").clicked() {
+                                        remove = Some(entry.clone());
+                                    }
+                                    if ui.button("Edit").clicked() {
+                                        edit = Some(entry.clone());
+                                    }
+                                    let mut autostart = entry.autostart;
+                                    if ui.checkbox(&mut autostart, "Autostart")
+                                        .on_hover_text("Launch at login from the XDG autostart folder")
+                                        .changed() {
+                                        toggle_autostart = Some((entry.clone(), autostart));
+                                    }
+                                });
+                            });
+                        });
+                }
+                if shown == 0 {
+                    ui.label(RichText::new("No matches").size(13.0).color(Color32::from_rgb(150, 150, 170)));
+                }
+            });
+        
+        if let Some(entry) = edit {
+            self.edit_installed_entry(&entry);
+        }
+        if let Some(entry) = remove {
+            if let Some(appname) = entry.path.file_stem() {
+                let result = self.remove_desktop_entry(&appname.to_string_lossy());
+                self.report(result);
+            }
+        }
+        if let Some((entry, enabled)) = toggle_autostart {
+            let result = self.set_installed_autostart(&entry, enabled);
+            self.report(result);
+            self.refresh_installed_entries();
+        }
+    }
+    
+    // Write or remove the autostart copy of an installed entry from the manage list
+    fn set_installed_autostart(&self, entry: &InstalledEntry, enabled: bool) -> Result<Outcome, AppError> {
+        let appname = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+        let content = fs::read_to_string(&entry.path)?;
+        self.sync_autostart(&appname, &content, enabled)?;
+        Ok(Outcome::Success(if enabled {
+            format!("{} will launch at login", entry.desktop.name)
+        } else {
+            format!("{} no longer launches at login", entry.desktop.name)
+        }))
+    }
+    
+    // Remove the desktop entry and installed binary for an app name. Either may
+    // already be gone; only a failure to delete something that exists is an error.
+    fn remove_desktop_entry(&mut self, appname: &str) -> Result<Outcome, AppError> {
+        let _span = tracing::info_span!("uninstall", app = appname).entered();
+        info!("Removing desktop entry for {}", appname);
+        
+        let applications_dir = self.app_dirs.applications.clone();
+        let desktop_file_path = self.desktop_file_path(appname)
+            .unwrap_or_else(|| applications_dir.join(desktop_file_name(appname)));
+        
+        // Only the binary Exec= points at is removed, and only if it's in the install dir.
+        // An in-place entry's Exec= is the user's own file, and a guessed name in the
+        // install dir could belong to something else.
+        let existing = DesktopEntry::from_file(&desktop_file_path).ok();
+        let exec_target = existing.as_ref()
+            .map(|existing| PathBuf::from(&existing.exec))
+            .filter(|exec| exec.starts_with(&self.install_dir));
+        
+        let mut removed = Vec::new();
+        let mut errors = Vec::new();
+        for path in std::iter::once(&desktop_file_path).chain(exec_target.as_ref()) {
+            let Ok(metadata) = fs::symlink_metadata(path) else {
+                debug!("Nothing to remove at {}", path.display());
+                continue;
+            };
+            
+            // The executable can be recovered from the trash; the .desktop file is cheap to recreate.
+            // A symlink from link mode is just removed, trashing could take its target along.
+            let trashable = Some(path) == exec_target.as_ref() && metadata.is_file() && !self.settings.trash_disabled;
+            if trashable {
+                match trash::delete(path) {
+                    Ok(_) => {
+                        info!("Moved {} to the trash", path.display());
+                        removed.push(format!("{} (moved to Trash)", path.display()));
+                        continue;
+                    }
+                    Err(e) => warn!("Couldn't move {} to the trash, deleting it instead: {}", path.display(), e),
+                }
+            }
+            
+            match fs::remove_file(path) {
+                Ok(_) => removed.push(path.display().to_string()),
+                Err(e) => {
+                    warn!("Couldn't remove {}: {}", path.display(), e);
+                    errors.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+        
+        if errors.is_empty() {
+            if let Some(existing) = &existing {
+                self.remove_themed_icons(appname, &existing.icon);
+            }
+        }
+        
+        // An autostart copy would keep launching a removed app
+        if let Some(autostart_path) = self.autostart_file_path(appname) {
+            if autostart_path.exists() {
+                if let Err(e) = fs::remove_file(&autostart_path) {
+                    warn!("Couldn't remove autostart entry: {}", e);
+                }
+            }
+        }
+        
+        if !self.skip_cache_update {
+            self.refresh_menu_caches(&applications_dir, &self.app_dirs.icons.join("hicolor"));
+        }
+        
+        if errors.is_empty() && !removed.is_empty() {
+            if let Some(existing) = &existing {
+                self.record_action("remove", existing);
+            }
+        }
+        self.refresh_installed_entries();
+        self.refresh_installed_state();
+        
+        if !errors.is_empty() {
+            Err(AppError::RemoveFailed(errors))
+        } else if removed.is_empty() {
+            Ok(Outcome::Warning(format!("Nothing to remove for {}", appname)))
+        } else {
+            Ok(Outcome::Success(format!("Removed {}", removed.join(" and "))))
+        }
+    }
+    
+    // Run update-desktop-database and gtk-update-icon-cache, if available
+    // Returns whether update-desktop-database succeeded
+    fn refresh_menu_caches(&self, applications_dir: &Path, icons_dir: &Path) -> bool {
+        info!("Updating desktop database...");
+        let database_updated = match Command::new("update-desktop-database")
+            .arg(applications_dir)
+            .status() {
+            Ok(status) => {
+                info!("update-desktop-database exited with: {}", status);
+                status.success()
+            }
+            Err(e) => {
+                warn!("Failed to run update-desktop-database: {}", e);
+                false
+            }
+        };
+
+        // Update the icon cache using gtk-update-icon-cache if available
+        info!("Updating icon cache...");
+        match Command::new("gtk-update-icon-cache")
+            .arg("-f")
+            .arg("-t")
+            .arg(icons_dir)
+            .status() {
+            Ok(status) => info!("gtk-update-icon-cache exited with: {}", status),
+            Err(e) => warn!("Failed to run gtk-update-icon-cache: {}", e),
+        };
+        database_updated
+    }
+    
+    // Manual refresh for when cache updates were skipped during creation
+    fn refresh_menu_caches_now(&mut self) {
+        self.refresh_menu_caches(&self.app_dirs.applications, &self.app_dirs.icons.join("hicolor"));
+        self.update_status(StatusLevel::Success, "Menu caches refreshed".to_string());
+    }
+    
+    // Show "Processing..." now and run the creation on the next frame, so the
+    // status is actually painted before the (blocking) work starts
+    fn request_create(&mut self, ctx: &egui::Context) {
+        // Ask before replacing an entry that already exists with this name
+        if let Some(path) = self.overwrite_target() {
+            info!("Desktop entry already exists, asking before overwriting: {}", path.display());
+            self.pending_overwrite = Some(path);
+            return;
+        }
+        self.start_create(ctx);
+    }
+    
+    // The existing desktop file Create would replace, unless a collision will be
+    // resolved by picking another name
+    fn overwrite_target(&self) -> Option<PathBuf> {
+        self.entry_id()
+            .filter(|appname| !self.rename_on_collision || self.unique_app_name(appname) == *appname)
+            .and_then(|appname| self.desktop_file_path(&appname))
+            .filter(|path| path.exists())
+    }
+    
+    fn start_create(&mut self, ctx: &egui::Context) {
+        debug_assert!(!self.work_in_progress(), "Create started while another one is running");
+        self.last_created = None;
+        self.update_status(StatusLevel::Info, "Processing...".to_string());
+        self.pending_create = true;
+        ctx.request_repaint();
+    }
+
+    // Run a creation requested last frame, now that "Processing..." has been shown
+    fn run_pending_create(&mut self, ctx: &egui::Context) {
+        if !std::mem::take(&mut self.pending_create) {
+            return;
+        }
+        debug_assert!(self.copy_job.is_none() && self.smoke_test_job.is_none(), "Create ran while another one is running");
+        // A background copy or test launch reports completion itself when done
+        if let Some(result) = self.create_desktop_entry().transpose() {
+            self.finish_create(ctx, result);
+        }
+    }
+
+    // Ctrl+O selects a file, Ctrl+I an icon and Ctrl+Enter creates the entry,
+    // each only when the matching button is enabled
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Leave the keyboard to an open dialog
+        if self.pending_overwrite.is_some() || self.error_modal.is_some() || self.close_prompt || self.purge_prompt {
+            return;
+        }
+        let pressed = |key: egui::Key| ctx.input(|i| i.modifiers.ctrl && i.key_pressed(key));
+        
+        if pressed(egui::Key::O) && self.batch.is_none() {
+            info!("Ctrl+O pressed");
+            self.select_executable();
+        } else if pressed(egui::Key::I) {
+            info!("Ctrl+I pressed");
+            self.select_icon(ctx);
+        } else if pressed(egui::Key::Enter) && self.appimage_path.is_some() && self.create_disabled_reason().is_none() {
+            info!("Ctrl+Enter pressed");
+            self.request_create(ctx);
+        }
+    }
+
+    // Modal with the full error text and a way to copy it into a bug report
+    fn show_error_modal(&mut self, ctx: &egui::Context) {
+        let Some(message) = &self.error_modal else {
+            return;
+        };
+        
+        let mut dismissed = false;
+        egui::Window::new("Error")
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("error_modal")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(message).monospace().color(Color32::from_rgb(255, 150, 150)));
+                    });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Copy to clipboard").clicked() {
+                        ctx.copy_text(message.clone());
+                    }
+                    if ui.button("Close").clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+        
+        if dismissed {
+            self.error_modal = None;
+        }
+    }
+    
+    // Confirmation modal shown before an existing desktop file is overwritten
+    fn show_overwrite_dialog(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.pending_overwrite.clone() else {
+            return;
+        };
+        // Show where the existing entry points so an update can be told apart from a name collision
+        let existing_exec = DesktopEntry::from_file(&path)
+            .ok()
+            .map(|existing| existing.exec)
+            .filter(|exec| !exec.is_empty())
+            .unwrap_or_else(|| "(unknown)".to_string());
+        
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Overwrite existing entry?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} already exists.", path.display()));
+                ui.add_space(5.0);
+                ui.label("It currently launches:");
+                ui.label(RichText::new(truncate_middle(&existing_exec, MAX_PATH_CHARS)).monospace())
+                    .on_hover_text(&existing_exec);
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Yes, overwrite").clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        
+        if confirmed {
+            self.pending_overwrite = None;
+            self.start_create(ctx);
+        } else if cancelled {
+            self.pending_overwrite = None;
+            self.launch_after_create = false;
+            self.update_status(StatusLevel::Info, "Cancelled, the existing entry was left untouched".to_string());
+        }
+    }
+    
+    // Confirm removing every DeskImage entry, listing what would go
+    fn show_purge_dialog(&mut self, ctx: &egui::Context) {
+        if !self.purge_prompt {
+            return;
+        }
+        let entries = self.purgeable_entries();
+        
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Remove all DeskImage entries?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                if entries.is_empty() {
+                    ui.label("There are no entries created by DeskImage.");
+                } else {
+                    ui.label(format!("These {} entries and their installed files will be removed:", entries.len()));
+                    egui::ScrollArea::vertical()
+                        .id_salt("purge_entries")
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for entry in &entries {
+                                ui.label(RichText::new(&entry.desktop.name).strong())
+                                    .on_hover_text(&entry.desktop.exec);
+                            }
+                        });
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!entries.is_empty(), egui::Button::new("Remove all")).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        
+        if confirmed {
+            self.purge_prompt = false;
+            let result = self.remove_all_entries();
+            self.report(result);
+        } else if cancelled {
+            self.purge_prompt = false;
+        }
+    }
+    
+    // Confirm quitting while a copy or folder install runs. Quitting cancels it;
+    // the copy thread removes its partial file before the window closes.
+    fn show_close_dialog(&mut self, ctx: &egui::Context) {
+        if !self.close_prompt {
+            return;
+        }
+        
+        let mut quit = false;
+        let mut stay = false;
+        egui::Window::new("Quit while working?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("An install is still running. Quitting cancels it and removes the partially copied file.");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel and quit").clicked() {
+                        quit = true;
+                    }
+                    if ui.button("Keep running").clicked() {
+                        stay = true;
+                    }
+                });
+            });
+        
+        if quit {
+            self.close_prompt = false;
+            self.quit_when_idle = true;
+            self.cancel_copy();
+            if let Some(batch) = &mut self.batch {
+                batch.queue.clear();
+            }
+        } else if stay {
+            self.close_prompt = false;
+        }
+    }
+    
+    // Explain why the Create button is disabled, or None if it can be used
+    fn create_disabled_reason(&self) -> Option<String> {
+        if self.pending_create || self.copy_job.is_some() || self.smoke_test_job.is_some() {
+            return Some("Already creating the desktop entry".to_string());
+        }
+        if self.batch.is_some() {
+            return Some("A folder of AppImages is being installed".to_string());
+        }
+        if self.pending_overwrite.is_some() {
+            return Some("Waiting for confirmation to overwrite the existing entry".to_string());
+        }
+        match &self.appimage_path {
+            None => Some("Select an AppImage file to enable this button".to_string()),
+            Some(path) if !path.exists() => Some(format!("The selected file no longer exists: {}", path.display())),
+            Some(_) if self.app_name.contains('/') => Some("The app name can't contain '/'".to_string()),
+            Some(_) if !self.app_id.trim().is_empty() && !is_valid_app_id(self.app_id.trim()) => {
+                Some("The app ID must be reverse-DNS, e.g. org.example.App".to_string())
+            }
+            Some(_) => None,
+        }
+    }
+
+    // Editable rows of id / name / command line for the desktop actions
+    fn show_actions_editor(&mut self, ui: &mut egui::Ui) {
+        let mut remove = None;
+        for (index, action) in self.actions.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut action.id)
+                    .hint_text("new-window")
+                    .desired_width(90.0));
+                ui.add(egui::TextEdit::singleline(&mut action.name)
+                    .hint_text("New Window")
+                    .desired_width(110.0));
+                ui.add(egui::TextEdit::singleline(&mut action.exec)
+                    .hint_text("Command, e.g. app --new-window")
+                    .desired_width(180.0));
+                if ui.small_button("✕").on_hover_text("Remove this action").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.actions.remove(index);
+        }
+        
+        if ui.small_button("Add action").clicked() {
+            self.actions.push(DesktopAction::default());
+        }
+    }
+    
+    // Render a small mock of the application menu row for the current selection
+    fn show_menu_preview(&self, ui: &mut egui::Ui) {
+        let name = self.effective_app_name().unwrap_or_else(|| "Application".to_string());
+        
+        egui::Frame::new()
+            .fill(Color32::from_rgb(40, 40, 52))
+            .corner_radius(6)
+            .stroke(Stroke::new(1.0, Color32::from_rgb(70, 70, 90)))
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let icon_size = Vec2::splat(32.0);
+                    match &self.icon_path {
+                        Some(icon_path) if icon_path.exists() => {
+                            ui.add(egui::Image::new(format!("file://{}", icon_path.display()))
+                                .fit_to_exact_size(icon_size));
+                        }
+                        _ => {
+                            // Same fallback the entry gets: application-x-executable
+                            ui.add_sized(icon_size, egui::Label::new(RichText::new("⚙").size(24.0)));
+                        }
+                    }
+                    
+                    ui.vertical(|ui| {
+                        ui.spacing_mut().item_spacing.y = 2.0;
+                        ui.label(RichText::new(&name).size(14.0).strong().color(Color32::from_rgb(230, 230, 240)));
+                        // Menus show GenericName= under the name, or Comment= without one
+                        let subtitle = [self.generic_name.trim(), self.comment.trim()]
+                            .into_iter()
+                            .find(|text| !text.is_empty())
+                            .unwrap_or("Application");
+                        ui.label(RichText::new(subtitle).size(12.0).color(Color32::from_rgb(150, 150, 170)));
+                    });
+                });
+            });
+    }
+
+    // The entry Create would write for the current form, worked out without
+    // touching the filesystem beyond reading an existing entry's icon
+    fn preview_entry(&self) -> Option<DesktopEntry> {
+        let appimage_path = self.appimage_path.as_ref()?;
+        let mut appname = self.entry_id()?;
+        if self.rename_on_collision {
+            appname = self.unique_app_name(&appname);
+        }
+        let bin_name = self.install_bin_name(&appname, &appimage_path.file_name()?.to_string_lossy());
+        
+        let exec_target = if self.system_wide {
+            Path::new(SYSTEM_BIN_DIR).join(&bin_name)
+        } else if self.launches_in_place(appimage_path) {
+            appimage_path.clone()
+        } else {
+            self.install_dir.join(&bin_name)
+        };
+        let desktop_file_path = if self.system_wide {
+            Some(Path::new(SYSTEM_APPLICATIONS_DIR).join(desktop_file_name(&appname)))
+        } else {
+            self.desktop_file_path(&appname)
+        };
+        let icon = if self.icon_path.as_ref().is_some_and(|icon| icon.exists()) {
+            appname.clone()
+        } else {
+            desktop_file_path
+                .and_then(|path| DesktopEntry::from_file(&path).ok())
+                .map(|existing| existing.icon)
+                .filter(|icon| !icon.is_empty())
+                .unwrap_or_else(|| "application-x-executable".to_string())
+        };
+        Some(self.desktop_entry_for(&appname, &exec_target, icon))
+    }
+    
+    // Read-only view of the .desktop file that would be written, rebuilt when the form changes
+    fn show_file_preview(&mut self, ui: &mut egui::Ui) {
+        // Building the entry reads existing desktop files, so only do it when the form changed
+        let key = PreviewKey {
+            options: self.install_options(),
+            install_dir: self.install_dir.clone(),
+            system_wide: self.system_wide,
+            rename_on_collision: self.rename_on_collision,
+            version: self.version.clone(),
+            zsync_url: self.zsync_url.clone().filter(|_| self.store_zsync_url),
+        };
+        if self.file_preview.as_ref().is_none_or(|(cached, _)| *cached != key) {
+            let content = self.preview_entry().map(|entry| entry.to_string()).unwrap_or_default();
+            self.file_preview = Some((key, content));
+        }
+        let Some((_, content)) = self.file_preview.as_ref().filter(|(_, content)| !content.is_empty()) else {
+            ui.label(RichText::new("Select a file to preview its desktop entry").size(12.0).color(Color32::from_rgb(150, 150, 170)));
+            return;
+        };
+        egui::ScrollArea::vertical()
+            .id_salt("desktop_file_preview")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut content.as_str())
+                    .code_editor()
+                    .desired_width(f32::INFINITY));
+            });
+    }
+
+    // Snapshot of the current form as reproducible install options
+    fn install_options(&self) -> Option<InstallOptions> {
+        Some(InstallOptions {
+            source: self.appimage_path.clone()?,
+            name: self.app_name.clone(),
+            app_id: self.app_id.clone(),
+            icon: self.icon_path.clone(),
+            categories: self.categories.clone(),
+            generic_name: self.generic_name.clone(),
+            comment: self.comment.clone(),
+            keywords: self.keywords.clone(),
+            mime_types: self.mime_types.clone(),
+            startup_wm_class: self.startup_wm_class.clone(),
+            exec_args: self.exec_args.clone(),
+            actions: self.actions.clone(),
+            terminal: self.run_in_terminal,
+            autostart: self.autostart,
+            keep_original_filename: self.keep_original_filename,
+            bin_name: self.bin_name.clone(),
+            install_mode: self.install_mode,
+            install_dir: Some(self.install_dir.clone()),
+            system_wide: self.system_wide,
+        })
+    }
+    
+    fn export_options(&mut self) {
+        let options = match self.install_options() {
+            Some(options) => options,
+            None => {
+                self.update_status(StatusLevel::Error, "Select an AppImage before exporting options".to_string());
+                return;
+            }
+        };
+        
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("DeskImage options", &["toml"])
+            .set_file_name(options.default_file_name())
+            .save_file() {
+            match options.save(&path) {
+                Ok(_) => self.update_status(StatusLevel::Success, format!("Options exported to {}", path.display())),
+                Err(e) => self.update_status(StatusLevel::Error, format!("Couldn't export options to {}: {}", path.display(), e)),
+            }
+        }
+    }
+    
+    fn import_options(&mut self, ctx: &egui::Context) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("DeskImage options", &["toml"])
+            .pick_file() {
+            match InstallOptions::load(&path) {
+                Ok(options) => {
+                    self.apply_options(options);
+                    self.load_icon_texture(ctx);
+                    self.update_status(StatusLevel::Info, format!("Loaded options from {}", path.display()));
+                }
+                Err(e) => self.update_status(StatusLevel::Error, format!("Couldn't load options from {}: {}", path.display(), e)),
+            }
+        }
+    }
+    
+    // Fill the form from imported options
+    fn apply_options(&mut self, options: InstallOptions) {
+        self.version = version_from_path(&options.source);
+        self.appimage_path = Some(options.source);
+        self.app_name = options.name;
+        self.app_id = options.app_id;
+        self.icon_path = options.icon;
+        self.categories = options.categories;
+        self.generic_name = options.generic_name;
+        self.comment = options.comment;
+        self.keywords = options.keywords;
+        self.mime_types = options.mime_types;
+        self.startup_wm_class = options.startup_wm_class;
+        self.exec_args = options.exec_args;
+        self.actions = options.actions;
+        self.run_in_terminal = options.terminal;
+        self.autostart = options.autostart;
+        self.keep_original_filename = options.keep_original_filename;
+        self.bin_name = options.bin_name;
+        self.install_mode = options.install_mode;
+        if let Some(install_dir) = options.install_dir {
+            self.install_dir = install_dir;
+        }
+        self.system_wide = options.system_wide;
+    }
+    
+    // Optionally draw attention to the window once an operation has finished
+    // `subject` names what was created, for the notification
+    fn signal_completion(&self, ctx: &egui::Context, subject: &str) {
+        // A folder install signals once at the end, not for every file
+        if self.batch.is_some() {
+            return;
+        }
+        
+        let (level, body) = &self.status;
+        let failed = *level == StatusLevel::Error;
+        let summary = if failed {
+            format!("Couldn't create {}", subject)
+        } else {
+            format!("Created {}", subject)
+        };
+        self.notify(&summary, body, failed);
+        
+        if !self.signal_on_completion {
+            return;
+        }
+        let attention = if failed {
+            egui::UserAttentionType::Critical
+        } else {
+            egui::UserAttentionType::Informational
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(attention));
+        
+        // Use the desktop's sound theme if libcanberra's player is around
+        let sound = if failed { "dialog-error" } else { "complete" };
+        if let Err(e) = Command::new("canberra-gtk-play").arg("-i").arg(sound).spawn() {
+            warn!("Couldn't play completion sound: {}", e);
+        }
+    }
+
+    fn completion_subject(&self) -> String {
+        match self.effective_app_name() {
+            Some(appname) => format!("the desktop entry for {}", appname),
+            None => "the desktop entry".to_string(),
+        }
+    }
+
+    // Desktop notification through notify-send, so the outcome is seen while minimized
+    fn notify(&self, summary: &str, body: &str, is_error: bool) {
+        if self.settings.notifications_disabled {
+            return;
+        }
+        let (urgency, icon) = if is_error {
+            ("critical", "dialog-error")
+        } else {
+            ("normal", "dialog-information")
+        };
+        match Command::new("notify-send")
+            .arg("--app-name=DeskImage")
+            .arg(format!("--urgency={}", urgency))
+            .arg(format!("--icon={}", icon))
+            .arg(summary)
+            .arg(body)
+            .spawn() {
+            Ok(_) => debug!("Sent notification: {}", summary),
+            Err(e) => warn!("Couldn't send notification: {}", e),
+        }
+    }
+
+    // Open a directory in the user's file manager, creating it first if needed
+    fn open_in_file_manager(&mut self, path: &Path) {
+        if let Err(e) = fs::create_dir_all(path) {
+            self.update_status(StatusLevel::Error, format!("Couldn't create directory {}: {}", path.display(), e));
+            return;
+        }
+        
+        match Command::new("xdg-open").arg(path).spawn() {
+            Ok(_) => info!("Opened {} in file manager", path.display()),
+            Err(e) => self.update_status(StatusLevel::Error, format!("Couldn't open {}: {}", path.display(), e)),
+        }
+    }
+
+    // Helper function to check if a file is executable
+    fn is_executable<P: AsRef<Path>>(&self, path: P) -> bool {
+        if let Ok(metadata) = fs::metadata(&path) {
+            let permissions = metadata.permissions();
+            let mode = permissions.mode();
+            return mode & 0o111 != 0; // Check if any executable bit is set
+        }
+        false
+    }
+
+    // Helper function to make a file executable
+    // Set 0755 and check it took effect; some filesystems accept the call but ignore it
+    fn make_executable<P: AsRef<Path>>(&self, path: P) -> Result<(), AppError> {
+        let path = path.as_ref();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+        if !self.is_executable(path) {
+            return Err(AppError::NotExecutable(path.to_path_buf()));
+        }
+        Ok(())
+    }
+}
+
+// Undo only lasts for the session; a command line install ends right away
+impl Drop for DeskImageApp {
+    fn drop(&mut self) {
+        if let Some(undo) = self.undo_state.take() {
+            discard_undo_backup(&undo);
+        }
+    }
+}
+
+impl eframe::App for DeskImageApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.run_pending_create(ctx);
+        self.poll_smoke_test(ctx);
+        self.poll_copy_job(ctx);
+        self.advance_batch(ctx);
+        self.poll_checksum(ctx);
+        self.poll_installed_state(ctx);
+        self.poll_bundled_job(ctx);
+        self.poll_applications_watch(ctx);
+        self.poll_update_job();
+        self.poll_wm_class_job();
+        self.handle_shortcuts(ctx);
+        
+        // Remember the window size, and write settings out when the window closes
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.settings.window_size = Some([rect.width(), rect.height()]);
+        }
+        if ctx.input(|i| i.viewport().close_requested()) {
+            if self.work_in_progress() {
+                // Closing now would leave a partial copy behind
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.close_prompt = !self.quit_when_idle;
+            } else {
+                self.save_settings();
+            }
+        }
+        if self.quit_when_idle {
+            if self.work_in_progress() {
+                ctx.request_repaint();
+            } else {
+                info!("Cancelled work has finished, closing");
+                self.quit_when_idle = false;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+        
+        // Configure the UI style for a modern look
+        let mut style = (*ctx.style()).clone();
+        style.spacing.item_spacing = Vec2::new(10.0, 15.0);
+        style.spacing.window_margin = Vec2::new(24.0, 24.0).into();
+        
+        // Dark theme
+        style.visuals.dark_mode = true;
+        style.visuals.panel_fill = Color32::from_rgb(22, 22, 30);
+        style.visuals.window_fill = Color32::from_rgb(22, 22, 30);
+        style.visuals.faint_bg_color = Color32::from_rgb(35, 35, 45);
+        style.visuals.extreme_bg_color = Color32::from_rgb(15, 15, 20);
+        
+        // Button styles
+        style.visuals.widgets.inactive.bg_fill = Color32::from_rgb(50, 50, 65);
+        style.visuals.widgets.hovered.bg_fill = Color32::from_rgb(70, 70, 90);
+        style.visuals.widgets.active.bg_fill = Color32::from_rgb(90, 90, 120);
+        style.visuals.widgets.inactive.bg_stroke = Stroke::new(1.0, Color32::from_rgb(80, 80, 100));
+        
+        // Apply the style
+        ctx.set_style(style);
         
         // We need to keep updating the UI to animate status messages
         if self.status_visible {
-            // Check if we need to repaint the UI
-            const STATUS_DURATION: std::time::Duration = std::time::Duration::from_secs(10);
             let time_since_status = self.status_timestamp.elapsed();
             
             if time_since_status < STATUS_DURATION {
                 // Request continuous repaints while the status is visible
                 ctx.request_repaint();
             } else {
-                // Keep status visible but stop continuous repaints after duration
+                // Stop repainting and go back to the neutral message, unless work is still running
                 self.status_visible = false;
+                if !self.work_in_progress() {
+                    self.status = (StatusLevel::Info, DEFAULT_STATUS.to_string());
+                }
             }
         }
         
-        egui::CentralPanel::default().show(ctx, |ui| {
+        // Store current status to detect changes
+        let previous_status = self.status.clone();
+        
+        // Footer
+        egui::TopBottomPanel::bottom("footer").show(ctx, |ui| {
             ui.vertical_centered(|ui| {
-                // Only show the header and installation section if not already installed
-                if !self.is_installed {
-                    ui.add_space(20.0);
-                    
-                    // Title with icon and styled text
-                    ui.heading(RichText::new("🖼️ DeskImage").size(32.0).strong());
-                    ui.add_space(5.0);
-                    ui.label(RichText::new("Create desktop entries for AppImage files").size(16.0).color(Color32::from_rgb(180, 180, 200)));
-                    
-                    ui.add_space(30.0);
-                    ui.separator();
-                    ui.add_space(30.0);
+                ui.add_space(5.0);
+                
+                // Troubleshooting shortcuts to DeskImage's own folders
+                ui.horizontal(|ui| {
+                    if ui.small_button("Open config folder")
+                        .on_hover_text("Open DeskImage's folder under XDG_CONFIG_HOME")
+                        .clicked() {
+                        match dirs::config_dir() {
+                            Some(dir) => self.open_in_file_manager(&dir.join("deskimage")),
+                            None => self.update_status(StatusLevel::Error, "Couldn't determine the config directory".to_string()),
+                        }
+                    }
+                    if ui.small_button("Open data folder")
+                        .on_hover_text("Open DeskImage's folder under XDG_DATA_HOME")
+                        .clicked() {
+                        match paths::data_dir() {
+                            Ok(dir) => self.open_in_file_manager(&dir.join("deskimage")),
+                            Err(e) => self.update_status(StatusLevel::Error, format!("{}", e)),
+                        }
+                    }
+                });
+                
+                ui.label(RichText::new("© 2025 DeskImage").color(Color32::from_rgb(120, 120, 140)).size(12.0));
+                ui.add_space(5.0);
+            });
+        });
+        
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // The form has outgrown small windows, so let it scroll
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    // Only show the header and installation section if not already installed
+                    if !self.is_installed {
+                        ui.add_space(20.0);
+                        
+                        // Title with icon and styled text
+                        ui.heading(RichText::new("🖼️ DeskImage").size(32.0).strong());
+                        ui.add_space(5.0);
+                        ui.label(RichText::new("Create desktop entries for AppImage files").size(16.0).color(Color32::from_rgb(180, 180, 200)));
+                        
+                        ui.add_space(30.0);
+                        ui.separator();
+                        ui.add_space(30.0);
+                        
+                        // Display installation section if not installed
+                        ui.scope(|ui| {
+                            ui.style_mut().visuals.extreme_bg_color = Color32::from_rgb(40, 30, 35);
+                            egui::Frame::new()
+                                .fill(Color32::from_rgb(40, 30, 35))
+                                .corner_radius(12)
+                                .stroke(Stroke::new(1.0, Color32::from_rgb(100, 60, 70)))
+                                .inner_margin(20.0)
+                                .show(ui, |ui| {
+                                    ui.vertical_centered(|ui| {
+                                        ui.label(RichText::new("DeskImage is not installed globally").color(Color32::from_rgb(255, 150, 150)).size(16.0));
+                                        ui.add_space(10.0);
+                                        
+                                        // Styled installation button
+                                        let button = egui::Button::new(RichText::new("Install to /usr/local/bin").size(16.0).strong())
+                                            .min_size(Vec2::new(250.0, 40.0))
+                                            .fill(Color32::from_rgb(80, 50, 60));
+                                        
+                                        if ui.add(button).clicked() {
+                                            self.install_globally();
+                                        }
+                                    });
+                                });
+                        });
+                        
+                        ui.add_space(20.0);
+                        ui.separator();
+                        ui.add_space(20.0);
+                    } else {
+                        // A simpler header for the installed version
+                        ui.add_space(20.0);
+                        ui.heading(RichText::new("🖼️ DeskImage").size(32.0).strong());
+                        ui.add_space(5.0);
+                        ui.label(RichText::new("Create desktop entries for AppImage files").size(16.0).color(Color32::from_rgb(180, 180, 200)));
+                        ui.add_space(20.0);
+                    }
                     
-                    // Display installation section if not installed
-                    ui.scope(|ui| {
-                        ui.style_mut().visuals.extreme_bg_color = Color32::from_rgb(40, 30, 35);
+                    // Persistent FUSE indicator, with guidance when it is missing
+                    if self.fuse_available {
+                        ui.label(RichText::new("✔ FUSE available").size(12.0).color(Color32::from_rgb(140, 200, 150)));
+                    } else {
                         egui::Frame::new()
-                            .fill(Color32::from_rgb(40, 30, 35))
-                            .corner_radius(12)
-                            .stroke(Stroke::new(1.0, Color32::from_rgb(100, 60, 70)))
-                            .inner_margin(20.0)
+                            .fill(Color32::from_rgb(45, 35, 20))
+                            .corner_radius(10)
+                            .stroke(Stroke::new(1.0, Color32::from_rgb(120, 90, 40)))
+                            .inner_margin(12.0)
                             .show(ui, |ui| {
                                 ui.vertical_centered(|ui| {
-                                    ui.label(RichText::new("DeskImage is not installed globally").color(Color32::from_rgb(255, 150, 150)).size(16.0));
-                                    ui.add_space(10.0);
-                                    
-                                    // Styled installation button
-                                    let button = egui::Button::new(RichText::new("Install to /usr/local/bin").size(16.0).strong())
-                                        .min_size(Vec2::new(250.0, 40.0))
-                                        .fill(Color32::from_rgb(80, 50, 60));
-                                    
-                                    if ui.add(button).clicked() {
-                                        self.install_globally();
-                                    }
+                                    ui.label(RichText::new("WARNING: FUSE (libfuse2) was not found").size(14.0).strong().color(Color32::from_rgb(255, 220, 150)));
+                                    ui.label(RichText::new("Most AppImages need it to run. Install it with your package manager, e.g.\n\
+                                        Debian/Ubuntu: sudo apt install libfuse2   Fedora: sudo dnf install fuse-libs   Arch: sudo pacman -S fuse2")
+                                        .size(12.0)
+                                        .color(Color32::from_rgb(220, 200, 160)));
                                 });
                             });
-                    });
+                    }
+                    ui.add_space(15.0);
                     
-                    ui.add_space(20.0);
-                    ui.separator();
-                    ui.add_space(20.0);
-                } else {
-                    // A simpler header for the installed version
-                    ui.add_space(20.0);
-                    ui.heading(RichText::new("🖼️ DeskImage").size(32.0).strong());
-                    ui.add_space(5.0);
-                    ui.label(RichText::new("Create desktop entries for AppImage files").size(16.0).color(Color32::from_rgb(180, 180, 200)));
-                    ui.add_space(20.0);
-                }
-                
-                // File selection section with modern styling
-                egui::Frame::new()
-                    .fill(Color32::from_rgb(30, 35, 45))
-                    .corner_radius(12)
-                    .stroke(Stroke::new(1.0, Color32::from_rgb(60, 70, 100)))
-                    .inner_margin(20.0)
-                    .show(ui, |ui| {
-                        ui.vertical_centered(|ui| {
-                            // Styled file selection button
-                            let select_button = egui::Button::new(RichText::new("Select AppImage File").size(16.0).strong())
-                                .min_size(Vec2::new(250.0, 45.0))
-                                .fill(Color32::from_rgb(60, 80, 120));
-                            
-                            if ui.add(select_button).clicked() {
-                                self.select_appimage();
-                            }
-                            
-                            ui.add_space(15.0);
-                            
-                            // Show selected file path with better styling
-                            ui.label(RichText::new("Selected file:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
-                            
-                            let path_text = if let Some(path) = &self.appimage_path {
-                                path.display().to_string()
-                            } else {
-                                "No file selected".to_string()
-                            };
-                            
-                            // Display the file path in a bordered frame
-                            egui::Frame::new()
-                                .fill(Color32::from_rgb(25, 25, 35))
-                                .corner_radius(8)
-                                .stroke(Stroke::new(1.0, Color32::from_rgb(50, 50, 70)))
-                                .inner_margin(10.0)
-                                .show(ui, |ui| {
-                                    ui.label(RichText::new(&path_text).monospace().size(14.0));
-                                });
-                            
-                            ui.add_space(20.0);
+                    // File selection section with modern styling
+                    egui::Frame::new()
+                        .fill(Color32::from_rgb(30, 35, 45))
+                        .corner_radius(12)
+                        .stroke(Stroke::new(1.0, Color32::from_rgb(60, 70, 100)))
+                        .inner_margin(20.0)
+                        .show(ui, |ui| {
+                            ui.vertical_centered(|ui| {
+                                // Styled file selection button
+                                let select_button = egui::Button::new(RichText::new("Select AppImage or Executable").size(16.0).strong())
+                                    .min_size(Vec2::new(250.0, 45.0))
+                                    .fill(Color32::from_rgb(60, 80, 120));
+                                
+                                if ui.add_enabled(self.batch.is_none(), select_button).on_hover_text("Ctrl+O").clicked() {
+                                    self.select_executable();
+                                }
+                                
+                                let folder_button = egui::Button::new(RichText::new("Select Folder").size(14.0))
+                                    .fill(Color32::from_rgb(50, 65, 95));
+                                if ui.add_enabled(self.batch.is_none() && self.copy_job.is_none(), folder_button)
+                                    .on_hover_text("Create entries for every AppImage in a folder")
+                                    .clicked() {
+                                    self.select_appimage_folder();
+                                }
+                                
+                                // One-click re-selection of earlier files, e.g. to install an update
+                                if !self.settings.recent_files.is_empty() && self.batch.is_none() {
+                                    let mut picked = None;
+                                    egui::CollapsingHeader::new("Recent files")
+                                        .id_salt("recent_files")
+                                        .show(ui, |ui| {
+                                            for path in &self.settings.recent_files {
+                                                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                                                if ui.link(name).on_hover_text(path.display().to_string()).clicked() {
+                                                    picked = Some(path.clone());
+                                                }
+                                            }
+                                        });
+                                    if let Some(path) = picked {
+                                        if path.exists() {
+                                            self.select_path(path);
+                                        } else {
+                                            self.settings.recent_files.retain(|recent| *recent != path);
+                                            self.update_status(StatusLevel::Warning, format!("{} no longer exists", path.display()));
+                                        }
+                                    }
+                                }
+                                
+                                ui.add_space(15.0);
+                                
+                                // Show selected file path with better styling
+                                ui.label(RichText::new("Selected file:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                
+                                let path_text = if let Some(path) = &self.appimage_path {
+                                    path.display().to_string()
+                                } else {
+                                    "No file selected".to_string()
+                                };
+                                
+                                // Display the file path in a bordered frame
+                                egui::Frame::new()
+                                    .fill(Color32::from_rgb(25, 25, 35))
+                                    .corner_radius(8)
+                                    .stroke(Stroke::new(1.0, Color32::from_rgb(50, 50, 70)))
+                                    .inner_margin(10.0)
+                                    .show(ui, |ui| {
+                                        ui.label(RichText::new(truncate_middle(&path_text, MAX_PATH_CHARS)).monospace().size(14.0))
+                                            .on_hover_text(&path_text);
+                                    });
+                                
+                                // Embedded update information tells whether in-place updates are possible
+                                if self.appimage_path.is_some() {
+                                    let update_text = match &self.update_info {
+                                        Some(info) => format!("Update info: {}", info),
+                                        None => "No update info".to_string(),
+                                    };
+                                    ui.label(RichText::new(truncate_middle(&update_text, MAX_PATH_CHARS)).size(12.0).color(Color32::from_rgb(150, 150, 170)))
+                                        .on_hover_text(&update_text);
+                                    ui.horizontal(|ui| {
+                                        if ui.small_button("Check for update")
+                                            .on_hover_text("Ask the AppImage for its update information")
+                                            .clicked() {
+                                            self.check_for_update();
+                                        }
+                                        if self.update_info.is_some() && self.update_tool_available {
+                                            let idle = self.update_job.is_none() && self.copy_job.is_none();
+                                            if ui.add_enabled(idle, egui::Button::new("Update installed copy").small())
+                                                .on_hover_text("Download the latest release in place with appimageupdatetool")
+                                                .clicked() {
+                                                self.start_update(ctx);
+                                            }
+                                        }
+                                    });
+                                    if let Some(version) = &self.version {
+                                        ui.label(RichText::new(format!("Version: {}", version)).size(12.0).color(Color32::from_rgb(150, 150, 170)))
+                                            .on_hover_text("From the filename, written as X-AppImage-Version");
+                                    }
+                                    // A .zsync file next to the AppImage means update tools can fetch newer releases
+                                    if let Some(zsync_url) = &self.zsync_url {
+                                        let zsync_text = format!("Updatable via zsync: {}", zsync_url);
+                                        ui.label(RichText::new(truncate_middle(&zsync_text, MAX_PATH_CHARS)).size(12.0).color(Color32::from_rgb(150, 150, 170)))
+                                            .on_hover_text(&zsync_text);
+                                        ui.checkbox(&mut self.store_zsync_url, "Store the zsync URL in the entry")
+                                            .on_hover_text("Written as X-AppImage-Zsync so update tools can find where the app came from");
+                                    }
+                                    
+                                    // Checksum to compare against the download page
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new("SHA-256:").size(12.0).color(Color32::from_rgb(150, 150, 170)));
+                                        match &self.checksum {
+                                            Some(checksum) => {
+                                                ui.label(RichText::new(checksum).monospace().size(11.0));
+                                                if ui.small_button("Copy").clicked() {
+                                                    ui.ctx().copy_text(checksum.clone());
+                                                }
+                                            }
+                                            None if self.checksum_receiver.is_some() => {
+                                                ui.label(RichText::new("computing…").size(12.0).color(Color32::from_rgb(150, 150, 170)));
+                                            }
+                                            None => {
+                                                ui.label(RichText::new("unavailable").size(12.0).color(Color32::from_rgb(150, 150, 170)));
+                                            }
+                                        }
+                                    });
+                                }
+                                
+                                ui.add_space(20.0);
 
-                            // Custom icon selection button
-                            let icon_button = egui::Button::new(RichText::new("Select Custom Icon").size(16.0).strong())
+                                // Custom icon selection button
+                                let icon_button = egui::Button::new(RichText::new("Select Custom Icon").size(16.0).strong())
+                                    .min_size(Vec2::new(250.0, 45.0))
+                                    .fill(Color32::from_rgb(60, 100, 100));
+                                
+                                if ui.add(icon_button).on_hover_text("Ctrl+I").clicked() {
+                                    self.select_icon(ctx);
+                                }
+                                
+                                ui.add_space(15.0);
+                                
+                                // Show selected icon path with styling
+                                ui.label(RichText::new("Custom icon:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                
+                                let icon_text = if let Some(path) = &self.icon_path {
+                                    path.display().to_string()
+                                } else {
+                                    "Default icon will be used".to_string()
+                                };
+                                
+                                // Display the icon path in a bordered frame
+                                egui::Frame::new()
+                                    .fill(Color32::from_rgb(25, 25, 35))
+                                    .corner_radius(8)
+                                    .stroke(Stroke::new(1.0, Color32::from_rgb(50, 50, 70)))
+                                    .inner_margin(10.0)
+                                    .show(ui, |ui| {
+                                        ui.horizontal(|ui| {
+                                            if let Some(texture) = &self.icon_texture {
+                                                ui.add(egui::Image::new(texture).fit_to_exact_size(Vec2::splat(64.0)));
+                                            }
+                                            ui.label(RichText::new(truncate_middle(&icon_text, MAX_PATH_CHARS)).monospace().size(14.0))
+                                                .on_hover_text(&icon_text);
+                                        });
+                                    });
+                                
+                                // Unpacking runs the AppImage, so it waits until asked for
+                                let unpackable = self.appimage_path.as_deref().is_some_and(appimage::is_appimage_file);
+                                if unpackable && self.bundled_dir.is_none() {
+                                    ui.add_space(8.0);
+                                    let unpacking = self.bundled_job.is_some();
+                                    let label = if unpacking { "Reading the AppImage..." } else { "Use the AppImage's icon and details" };
+                                    if ui.add_enabled(!unpacking, egui::Button::new(label))
+                                        .on_hover_text("Runs the AppImage with --appimage-extract to unpack its icons and desktop file. Check its SHA-256 first if you don't trust it.")
+                                        .clicked() {
+                                        self.start_bundled_extraction();
+                                    }
+                                }
+                                
+                                // Pick one of the icons bundled in the AppImage; the first is used if none is picked
+                                if !self.bundled_icons.is_empty() {
+                                    ui.add_space(8.0);
+                                    ui.label(RichText::new("Icons in the AppImage:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                    let mut picked = None;
+                                    ui.horizontal_wrapped(|ui| {
+                                        for icon in &self.bundled_icons {
+                                            let selected = self.icon_path.as_ref() == Some(icon);
+                                            let image = egui::Image::new(format!("file://{}", icon.display()))
+                                                .fit_to_exact_size(Vec2::splat(48.0));
+                                            let name = icon.file_name().unwrap_or_default().to_string_lossy();
+                                            let hover = match png_width(icon) {
+                                                Some(width) => format!("{} ({}px)", name, width),
+                                                None => name.to_string(),
+                                            };
+                                            if ui.add(egui::ImageButton::new(image).selected(selected)).on_hover_text(hover).clicked() {
+                                                picked = Some(icon.clone());
+                                            }
+                                        }
+                                    });
+                                    if let Some(icon) = picked {
+                                        self.icon_path = Some(icon);
+                                        self.load_icon_texture(ctx);
+                                    }
+                                }
+                                
+                                ui.add_space(20.0);
+                                
+                                // Editable app name, pre-filled from the filename
+                                ui.label(RichText::new("App name:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                ui.add(egui::TextEdit::singleline(&mut self.app_name)
+                                    .hint_text("Leave empty to use the cleaned filename")
+                                    .desired_width(300.0));
+                                
+                                ui.add_space(10.0);
+                                
+                                // Where the executable gets installed
+                                ui.label(RichText::new("Install directory:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                ui.horizontal(|ui| {
+                                    let install_dir_text = self.install_dir.display().to_string();
+                                    ui.label(RichText::new(truncate_middle(&install_dir_text, MAX_PATH_CHARS)).monospace().size(13.0))
+                                        .on_hover_text(&install_dir_text);
+                                    if ui.button("Change…").clicked() {
+                                        self.select_install_dir();
+                                    }
+                                });
+                                ui.add_enabled_ui(!self.system_wide, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.radio_value(&mut self.install_mode, InstallMode::Copy, "Copy")
+                                            .on_hover_text("Copy the AppImage into the install directory");
+                                        ui.radio_value(&mut self.install_mode, InstallMode::Symlink, "Symlink")
+                                            .on_hover_text("Symlink the AppImage into the install directory instead of copying it. The original must stay where it is.");
+                                        ui.radio_value(&mut self.install_mode, InstallMode::InPlace, "In place")
+                                            .on_hover_text("Point the entry at the original file, nothing is added to the install directory. The original must stay where it is.");
+                                    });
+                                });
+                                ui.checkbox(&mut self.system_wide, "System-wide (all users)")
+                                    .on_hover_text(format!("Install to {} and {} instead. Asks for your password.", SYSTEM_BIN_DIR, SYSTEM_APPLICATIONS_DIR));
+                                
+                                ui.add_space(10.0);
+                                
+                                // Category chips
+                                ui.label(RichText::new("Categories:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                self.show_main_category_picker(ui);
+                                self.show_category_chips(ui);
+                                
+                                ui.add_space(10.0);
+                                ui.checkbox(&mut self.run_in_terminal, "Run in terminal")
+                                    .on_hover_text("For command-line tools: open a terminal window when launched (Terminal=true)");
+                                
+                                // Rarely needed fields, collapsed unless opened in an earlier session
+                                let advanced = egui::CollapsingHeader::new("Advanced")
+                                    .id_salt("advanced_options")
+                                    .default_open(self.settings.advanced_expanded)
+                                    .show(ui, |ui| {
+                                        // Optional reverse-DNS ID used for the file names instead of the app name
+                                        ui.label(RichText::new("App ID:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::singleline(&mut self.app_id)
+                                            .hint_text("Optional, e.g. com.example.App")
+                                            .desired_width(300.0))
+                                            .on_hover_text("Names the .desktop file and installed binary; the menu still shows the app name");
+                                        let app_id = self.app_id.trim();
+                                        if !app_id.is_empty() && !is_valid_app_id(app_id) {
+                                            ui.label(RichText::new("Not a reverse-DNS ID: use two or more dot-separated parts of letters, digits, _ and -, not starting with a digit")
+                                                .size(12.0)
+                                                .color(Color32::from_rgb(255, 200, 120)));
+                                        }
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Extra search terms for the menu
+                                        ui.label(RichText::new("Keywords:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::singleline(&mut self.keywords)
+                                            .hint_text("Semicolon separated, e.g. editor;notes;")
+                                            .desired_width(300.0));
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Kind of application, shown by some menus next to the name
+                                        ui.label(RichText::new("Generic name:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::singleline(&mut self.generic_name)
+                                            .hint_text("Optional, e.g. Web Browser")
+                                            .desired_width(300.0));
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // File types this app should open by default
+                                        ui.label(RichText::new("MIME types:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::singleline(&mut self.mime_types)
+                                            .hint_text("Semicolon separated, e.g. text/markdown;application/x-foo;")
+                                            .desired_width(300.0));
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Description shown as a tooltip in menus and overviews
+                                        ui.label(RichText::new("Comment:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::multiline(&mut self.comment)
+                                            .hint_text("Short description of the app")
+                                            .desired_rows(2)
+                                            .desired_width(300.0));
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Lets docks match running windows to this entry
+                                        ui.label(RichText::new("Window class (StartupWMClass):").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.horizontal(|ui| {
+                                            ui.add(egui::TextEdit::singleline(&mut self.startup_wm_class)
+                                                .hint_text("Optional, fixes generic taskbar icons")
+                                                .desired_width(200.0));
+                                            let detecting = self.wm_class_job.is_some();
+                                            let detect_label = if detecting { "Detecting..." } else { "Detect" };
+                                            if ui.add_enabled(self.appimage_path.is_some() && !detecting, egui::Button::new(detect_label))
+                                                .on_hover_text("Launches the app for a few seconds and reads its window class with xprop")
+                                                .clicked() {
+                                                self.start_wm_class_detection(ctx);
+                                            }
+                                        });
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Flags and field codes appended to Exec= after the program
+                                        ui.label(RichText::new("Arguments:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        ui.add(egui::TextEdit::singleline(&mut self.exec_args)
+                                            .hint_text("e.g. --no-sandbox %U")
+                                            .desired_width(300.0))
+                                            .on_hover_text("Added after the program path as written. %U/%F pass opened files; write %% for a literal %.");
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        // Extra entries for the launcher's right-click menu
+                                        ui.label(RichText::new("Actions (right-click menu):").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        self.show_actions_editor(ui);
+                                        
+                                        ui.add_space(10.0);
+                                        
+                                        ui.label(RichText::new("Installed binary name:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                        let default_bin_name = self.entry_id()
+                                            .zip(self.appimage_path.as_ref().and_then(|path| path.file_name()))
+                                            .map(|(appname, original)| self.default_bin_name(&appname, &original.to_string_lossy()))
+                                            .unwrap_or_default();
+                                        ui.add(egui::TextEdit::singleline(&mut self.bin_name)
+                                            .hint_text(default_bin_name)
+                                            .desired_width(300.0))
+                                            .on_hover_text("File name in the install directory, used by Exec=. The menu still shows the app name.");
+                                        if !is_valid_bin_name(self.bin_name.trim()) {
+                                            ui.label(RichText::new("Can't contain '/' or be \".\" or \"..\"")
+                                                .size(12.0)
+                                                .color(Color32::from_rgb(255, 200, 120)));
+                                        }
+                                        ui.checkbox(&mut self.keep_original_filename, "Keep the original filename for the installed binary")
+                                            .on_hover_text("Install to ~/.local/bin under the AppImage's filename instead of the menu name");
+                                        ui.checkbox(&mut self.rename_on_collision, "Add a number to the name if it's taken by another app")
+                                            .on_hover_text("Creates e.g. \"App-2.desktop\" instead of asking to overwrite an entry for a different executable");
+                                        ui.checkbox(&mut self.autostart, "Launch at login (autostart)")
+                                            .on_hover_text("Also place the entry in the XDG autostart folder");
+                                    });
+                                if advanced.header_response.clicked() {
+                                    self.settings.advanced_expanded = !self.settings.advanced_expanded;
+                                    self.save_settings();
+                                }
+                                
+                                ui.add_space(20.0);
+                                
+                                // Mock menu item showing how the entry will look once installed
+                                ui.label(RichText::new("Menu preview:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
+                                self.show_menu_preview(ui);
+                                
+                                let preview_label = if self.file_preview_open { "Hide Preview" } else { "Preview" };
+                                if ui.button(preview_label)
+                                    .on_hover_text("Show the .desktop file Create would write, without writing anything")
+                                    .clicked() {
+                                    self.file_preview_open = !self.file_preview_open;
+                                }
+                                if self.file_preview_open {
+                                    self.show_file_preview(ui);
+                                }
+                                
+                                ui.add_space(20.0);
+                                
+                                // Create desktop entry button with conditional styling
+                                let disabled_reason = self.create_disabled_reason();
+                                let create_label = if self.installed_state.is_some() { "Update Desktop Entry" } else { "Create Desktop Entry" };
+                                let create_button = egui::Button::new(
+                                    RichText::new(create_label).size(16.0).strong()
+                                )
                                 .min_size(Vec2::new(250.0, 45.0))
-                                .fill(Color32::from_rgb(60, 100, 100));
-                            
-                            if ui.add(icon_button).clicked() {
-                                self.select_icon();
-                            }
-                            
-                            ui.add_space(15.0);
-                            
-                            // Show selected icon path with styling
-                            ui.label(RichText::new("Custom icon:").size(14.0).color(Color32::from_rgb(170, 170, 190)));
-                            
-                            let icon_text = if let Some(path) = &self.icon_path {
-                                path.display().to_string()
-                            } else {
-                                "Default icon will be used".to_string()
-                            };
-                            
-                            // Display the icon path in a bordered frame
-                            egui::Frame::new()
-                                .fill(Color32::from_rgb(25, 25, 35))
-                                .corner_radius(8)
-                                .stroke(Stroke::new(1.0, Color32::from_rgb(50, 50, 70)))
-                                .inner_margin(10.0)
-                                .show(ui, |ui| {
-                                    ui.label(RichText::new(&icon_text).monospace().size(14.0));
+                                .fill(if disabled_reason.is_none() {
+                                    Color32::from_rgb(60, 120, 80)
+                                } else {
+                                    Color32::from_rgb(60, 60, 70)
+                                });
+                                
+                                // While a copy runs the Create button becomes a Cancel button
+                                if self.copy_job.is_some() {
+                                    let cancel_button = egui::Button::new(RichText::new("Cancel").size(16.0).strong())
+                                        .min_size(Vec2::new(250.0, 45.0))
+                                        .fill(Color32::from_rgb(120, 60, 60));
+                                    let cancelling = self.copy_job.as_ref().is_some_and(|job| job.cancel_flag.load(Ordering::Relaxed));
+                                    if ui.add_enabled(!cancelling, cancel_button).clicked() {
+                                        info!("Cancel button clicked");
+                                        self.cancel_copy();
+                                    }
+                                } else {
+                                    let mut create_response = ui.add_enabled(disabled_reason.is_none(), create_button);
+                                    if let Some(reason) = &disabled_reason {
+                                        create_response = create_response.on_disabled_hover_text(reason);
+                                        ui.label(RichText::new(reason).size(12.0).color(Color32::from_rgb(170, 170, 190)));
+                                    }
+                                    
+                                    let create_hover = match &self.installed_state {
+                                        Some(installed) => format!("Replaces {} (Ctrl+Enter)", installed.desktop_path.display()),
+                                        None => "Ctrl+Enter".to_string(),
+                                    };
+                                    if create_response.on_hover_text(create_hover).clicked() {
+                                        info!("Create Desktop Entry button clicked");
+                                        self.request_create(ctx);
+                                    }
+                                    
+                                    let launch_button = egui::Button::new(RichText::new("Install and Launch").size(14.0))
+                                        .fill(Color32::from_rgb(50, 90, 110));
+                                    if ui.add_enabled(disabled_reason.is_none(), launch_button)
+                                        .on_hover_text("Create the entry, then start the installed app to check it runs")
+                                        .clicked() {
+                                        info!("Install and Launch button clicked");
+                                        self.launch_after_create = true;
+                                        self.request_create(ctx);
+                                    }
+                                }
+                                
+                                if let Some(progress) = self.copy_progress {
+                                    ui.add(egui::ProgressBar::new(progress)
+                                        .desired_width(250.0)
+                                        .show_percentage()
+                                        .animate(true));
+                                }
+                                
+                                // Remove button, only useful once an entry with this name exists
+                                let existing_entry = self.entry_id()
+                                    .filter(|appname| self.desktop_file_path(appname).is_some_and(|path| path.exists()));
+                                let remove_button = egui::Button::new(RichText::new("Remove Entry").size(16.0).strong())
+                                    .min_size(Vec2::new(250.0, 45.0))
+                                    .fill(if existing_entry.is_some() {
+                                        Color32::from_rgb(140, 50, 60)
+                                    } else {
+                                        Color32::from_rgb(60, 60, 70)
+                                    });
+                                if ui.add_enabled(existing_entry.is_some() && self.copy_job.is_none(), remove_button)
+                                    .on_disabled_hover_text("No desktop entry exists for this app name")
+                                    .clicked() {
+                                    if let Some(appname) = existing_entry {
+                                        let result = self.remove_desktop_entry(&appname);
+                                        self.report(result);
+                                    }
+                                }
+                                let mut use_trash = !self.settings.trash_disabled;
+                                if ui.checkbox(&mut use_trash, "Move removed AppImages to Trash").changed() {
+                                    self.settings.trash_disabled = !use_trash;
+                                    self.save_settings();
+                                }
+                                
+                                // The override only makes sense right after a failed strict-mode test
+                                if self.smoke_test_failed {
+                                    let create_anyway = egui::Button::new(RichText::new("Create Anyway").size(14.0))
+                                        .fill(Color32::from_rgb(120, 90, 40));
+                                    if ui.add(create_anyway).on_hover_text("Skip the test launch for this attempt").clicked() {
+                                        self.skip_smoke_test_once = true;
+                                        self.request_create(ctx);
+                                    }
+                                }
+                                
+                                ui.checkbox(&mut self.smoke_test, "Strict mode: test-launch the AppImage before creating")
+                                    .on_hover_text("Launches the app for a few seconds. Some apps legitimately exit quickly, so this is off by default.");
+                                ui.checkbox(&mut self.signal_on_completion, "Flash window and play a sound when done");
+                                if ui.checkbox(&mut self.settings.verify_copy, "Verify after copy")
+                                    .on_hover_text("Compare SHA-256 of the source and the copy. Reads both files again, so large files take longer.")
+                                    .changed() {
+                                    self.save_settings();
+                                }
+                                let mut notifications = !self.settings.notifications_disabled;
+                                if ui.checkbox(&mut notifications, "Show a desktop notification when done").changed() {
+                                    self.settings.notifications_disabled = !notifications;
+                                    self.save_settings();
+                                }
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut self.skip_cache_update, "Skip menu cache update")
+                                        .on_hover_text("Don't run update-desktop-database/gtk-update-icon-cache. The menu may need a manual refresh or re-login.");
+                                    if ui.small_button("Refresh menu caches").clicked() {
+                                        self.refresh_menu_caches_now();
+                                    }
                                 });
-                            
-                            ui.add_space(20.0);
-                            
-                            // Create desktop entry button with conditional styling
-                            let create_button = egui::Button::new(
-                                RichText::new("Create Desktop Entry").size(16.0).strong()
-                            )
-                            .min_size(Vec2::new(250.0, 45.0))
-                            .fill(if self.appimage_path.is_some() {
-                                Color32::from_rgb(60, 120, 80)
-                            } else {
-                                Color32::from_rgb(60, 60, 70)
-                            });
-                            
-                            if ui.add_enabled(self.appimage_path.is_some(), create_button).clicked() {
-                                println!("Create Desktop Entry button clicked");
                                 
-                                // Change the status message immediately to show we're processing
-                                self.update_status("Processing...".to_string());
+                                // Save or restore the form as a reproducible options file
+                                ui.horizontal(|ui| {
+                                    if ui.add_enabled(self.appimage_path.is_some(), egui::Button::new("Export options…")).clicked() {
+                                        self.export_options();
+                                    }
+                                    if ui.button("Import options…").clicked() {
+                                        self.import_options(ctx);
+                                    }
+                                });
+                            });
+                        });
+                    
+                    ui.add_space(25.0);
+                    
+                    // Status message with more visual separation and styling
+                    let (status_color, status_bg, status_border) = status_palette(ui.visuals().dark_mode, self.status.0);
+                    let alpha = self.status_alpha();
+                    let (status_color, status_bg, status_border) = (
+                        status_color.gamma_multiply(alpha),
+                        status_bg.gamma_multiply(alpha),
+                        status_border.gamma_multiply(alpha),
+                    );
+                    
+                    // Create pulsing effect for new status messages
+                    let border_width = if self.status_visible {
+                        // Calculate a pulsing border width between 1.0 and 3.0
+                        let time_since_status = self.status_timestamp.elapsed().as_secs_f32();
+                        let pulse = (time_since_status * 3.0).sin() * 0.5 + 0.5; // oscillate between 0.0 and 1.0
+                        1.0 + pulse * 2.0 // between 1.0 and 3.0
+                    } else {
+                        1.0 // default border width
+                    };
+                    
+                    // Debug text to show in UI
+                    let debug_text = format!(
+                        "Status Message: {:?} {}\nStatus age: {:.1}s\nVisible: {}\nOpacity: {:.2}", 
+                        self.status.0,
+                        self.status.1,
+                        self.status_timestamp.elapsed().as_secs_f32(),
+                        self.status_visible,
+                        alpha
+                    );
+                    
+                    egui::Frame::new()
+                        .fill(status_bg)
+                        .corner_radius(10)
+                        .stroke(Stroke::new(border_width, status_border)) // Make border pulse
+                        .inner_margin(20.0) // Increase margin
+                        .show(ui, |ui| {
+                            ui.vertical(|ui| {
+                                let (level, message) = &self.status;
+                                ui.heading(RichText::new(format!("{}{}", level.prefix(), message)).size(16.0).color(status_color).strong());
                                 
-                                // Then create the desktop entry
-                                self.create_desktop_entry();
+                                // Display debug info in smaller text
+                                if self.debug_mode {
+                                    ui.add_space(10.0);
+                                    ui.label(RichText::new(&debug_text).size(12.0).color(Color32::from_rgb(180, 180, 180)));
+                                }
+                            });
+                        });
+                    
+                    // Inspect the files of the entry that was just created
+                    if let Some((desktop_file, exec_target)) = self.last_created.clone() {
+                        ui.add_space(10.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Open containing folder")
+                                .on_hover_text(desktop_file.display().to_string())
+                                .clicked() {
+                                if let Some(dir) = desktop_file.parent() {
+                                    self.open_in_file_manager(dir);
+                                }
+                            }
+                            if ui.button("Open install folder")
+                                .on_hover_text(exec_target.display().to_string())
+                                .clicked() {
+                                if let Some(dir) = exec_target.parent() {
+                                    self.open_in_file_manager(dir);
+                                }
                             }
                         });
-                    });
-                
-                ui.add_space(25.0);
-                
-                // Status message with more visual separation and styling
-                let (status_color, status_bg, status_border) = if self.status_message.starts_with("SUCCESS") {
-                    (Color32::from_rgb(180, 255, 180), Color32::from_rgb(25, 45, 30), Color32::from_rgb(60, 120, 80))
-                } else if self.status_message.starts_with("ERROR") {
-                    (Color32::from_rgb(255, 180, 180), Color32::from_rgb(45, 25, 30), Color32::from_rgb(120, 60, 80))
-                } else if self.status_message.starts_with("WARNING") {
-                    (Color32::from_rgb(255, 220, 150), Color32::from_rgb(45, 35, 20), Color32::from_rgb(120, 90, 40))
-                } else {
-                    (Color32::from_rgb(220, 220, 220), Color32::from_rgb(35, 35, 45), Color32::from_rgb(70, 70, 90))
-                };
-                
-                // Create pulsing effect for new status messages
-                let border_width = if self.status_visible {
-                    // Calculate a pulsing border width between 1.0 and 3.0
-                    let time_since_status = self.status_timestamp.elapsed().as_secs_f32();
-                    let pulse = (time_since_status * 3.0).sin() * 0.5 + 0.5; // oscillate between 0.0 and 1.0
-                    1.0 + pulse * 2.0 // between 1.0 and 3.0
-                } else {
-                    1.0 // default border width
-                };
-                
-                // Debug text to show in UI
-                let debug_text = format!(
-                    "Status Message: {}\nStatus age: {:.1}s\nVisible: {}", 
-                    self.status_message,
-                    self.status_timestamp.elapsed().as_secs_f32(),
-                    self.status_visible
-                );
-                
-                egui::Frame::new()
-                    .fill(status_bg)
-                    .corner_radius(10)
-                    .stroke(Stroke::new(border_width, status_border)) // Make border pulse
-                    .inner_margin(20.0) // Increase margin
-                    .show(ui, |ui| {
-                        ui.vertical(|ui| {
-                            ui.heading(RichText::new(&self.status_message).size(16.0).color(status_color).strong());
-                            
-                            // Display debug info in smaller text
-                            ui.add_space(10.0);
-                            ui.label(RichText::new(&debug_text).size(12.0).color(Color32::from_rgb(180, 180, 180)));
-                        });
-                    });
-                
-                ui.add_space(20.0);
-                
-                // Footer
-                ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
-                    ui.label(RichText::new("© 2025 DeskImage").color(Color32::from_rgb(120, 120, 140)).size(12.0));
-                    ui.add_space(5.0);
+                    }
+                    
+                    // Offered until the next overwrite or until the window closes
+                    if let Some(undo) = &self.undo_state {
+                        let label = format!("Undo overwrite of {}", undo.appname);
+                        if ui.add_enabled(!self.work_in_progress(), egui::Button::new(label))
+                            .on_hover_text("Restore the desktop entry and binary the last Create replaced")
+                            .clicked() {
+                            self.undo_last_overwrite();
+                        }
+                    }
+                    
+                    ui.add_space(20.0);
+                    
+                    self.show_batch_log(ui);
+                    
+                    ui.separator();
+                    ui.add_space(10.0);
+                    
+                    // Everything DeskImage has installed so far
+                    self.show_installed_entries(ui);
+                    
+                    ui.add_space(20.0);
                 });
             });
         });
         
+        self.show_overwrite_dialog(ctx);
+        self.show_error_modal(ctx);
+        self.show_close_dialog(ctx);
+        self.show_purge_dialog(ctx);
+        
         // If status message changed, update the timestamp and visibility
-        if previous_status != self.status_message {
-            println!("Status message changed: {}", self.status_message);
+        if previous_status != self.status {
+            debug!("Status message changed: {}", self.status.1);
             self.status_timestamp = std::time::Instant::now();
             self.status_visible = true;
             ctx.request_repaint();
@@ -661,10 +4006,11 @@ impl eframe::App for DeskImageApp {
     }
 }
 
-pub fn run_gui() -> Result<(), eframe::Error> {
+pub fn run_gui(app_dirs: AppDirs) -> Result<(), eframe::Error> {
+    let window_size = Settings::load().window_size.unwrap_or([650.0, 600.0]);
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([650.0, 600.0])
+            .with_inner_size(window_size)
             .with_min_inner_size([500.0, 400.0])
             .with_title("DeskImage")
             .with_decorations(true),
@@ -674,6 +4020,915 @@ pub fn run_gui() -> Result<(), eframe::Error> {
     eframe::run_native(
         "DeskImage",
         options,
-        Box::new(|_cc| Ok(Box::new(DeskImageApp::default())))
+        Box::new(|cc| {
+            // Needed so the menu preview can show PNG/SVG icon thumbnails
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            let mut app = DeskImageApp::new(app_dirs);
+            app.watch_applications_dir(&cc.egui_ctx);
+            Ok(Box::new(app))
+        })
     )
-} 
\ No newline at end of file
+}
+
+// Turn a filename like "Some_App-2.3.1-x86_64.AppImage" into "Some App":
+// drop the extension, architecture/platform and version suffixes, then
+// turn separators into spaces and title-case the words
+fn clean_app_name(filename: &str) -> String {
+    let base = strip_platform_suffixes(filename);
+    let base = VERSION_SUFFIX.replace(&base, "").to_string();
+    
+    let name = NAME_SEPARATORS
+        .replace_all(&base, " ")
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    
+    if name.is_empty() {
+        clean_app_name_legacy(filename)
+    } else {
+        name
+    }
+}
+
+// A filename without its .AppImage extension and architecture/platform suffixes,
+// which can be stacked, e.g. "-linux-x86_64"
+fn strip_platform_suffixes(filename: &str) -> String {
+    let mut base = APPIMAGE_EXTENSION.replace(filename, "").to_string();
+    while PLATFORM_SUFFIX.is_match(&base) {
+        base = PLATFORM_SUFFIX.replace(&base, "").to_string();
+    }
+    base
+}
+
+// The version chunk clean_app_name strips from a filename, e.g. "2.3.1" from
+// "Some_App-2.3.1-x86_64.AppImage" or "1.0.0-beta.2" from "App-v1.0.0-beta.2.AppImage"
+fn version_from_path(path: &Path) -> Option<String> {
+    let filename = path.file_name()?.to_string_lossy();
+    let base = strip_platform_suffixes(&filename);
+    Some(VERSION_SUFFIX.captures(&base)?[1].to_string())
+}
+
+// Whether an ID follows the reverse-DNS rules for desktop file IDs: at least two
+// dot-separated segments of ASCII letters, digits, '_' and '-', none empty or
+// starting with a digit, at most 255 characters in total
+fn is_valid_app_id(id: &str) -> bool {
+    let segments: Vec<&str> = id.split('.').collect();
+    id.len() <= 255
+        && segments.len() >= 2
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with(|c: char| c.is_ascii_digit())
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+// Applications directories in lookup order: XDG_DATA_HOME first, then each of
+// XDG_DATA_DIRS. An earlier directory wins when two contain the same desktop file ID.
+fn applications_dirs() -> Vec<PathBuf> {
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    
+    let mut roots: Vec<PathBuf> = paths::data_dir().into_iter().collect();
+    for dir in data_dirs.split(':').filter(|dir| !dir.is_empty()) {
+        let dir = PathBuf::from(dir);
+        if !roots.contains(&dir) {
+            roots.push(dir);
+        }
+    }
+    roots.into_iter().map(|dir| dir.join("applications")).collect()
+}
+
+// Desktop files below an applications directory with their desktop file IDs
+// (the relative path with '/' replaced by '-')
+fn desktop_files_with_ids(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    for dir_entry in read_dir.flatten() {
+        let path = dir_entry.path();
+        let file_name = dir_entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            desktop_files_with_ids(&path, &format!("{}{}-", prefix, file_name), files);
+        } else if file_name.ends_with(".desktop") {
+            files.push((format!("{}{}", prefix, file_name), path));
+        }
+    }
+}
+
+// Describe every other entry across the applications directories whose Name=
+// matches `name`, and which one the menu shows
+fn find_name_collisions(own_file: &Path, name: &str) -> Vec<String> {
+    let dirs = applications_dirs();
+    let own_id = own_file.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let own_rank = own_file.parent()
+        .and_then(|parent| dirs.iter().position(|dir| dir == parent))
+        .unwrap_or(0);
+    
+    let mut collisions = Vec::new();
+    for (rank, dir) in dirs.iter().enumerate() {
+        let mut files = Vec::new();
+        desktop_files_with_ids(dir, "", &mut files);
+        for (id, path) in files {
+            if path == own_file {
+                continue;
+            }
+            let Ok(other) = DesktopEntry::from_file(&path) else {
+                continue;
+            };
+            if other.name.trim() != name.trim() {
+                continue;
+            }
+            let outcome = if id != own_id {
+                "both show up in the menu"
+            } else if rank < own_rank {
+                "it takes precedence and hides yours"
+            } else {
+                "yours takes precedence and hides it"
+            };
+            collisions.push(format!("{} ({})", path.display(), outcome));
+        }
+    }
+    collisions
+}
+
+// Whether an executable with this name is on PATH, like `which`
+fn command_exists(name: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        fs::metadata(dir.join(name)).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+    })
+}
+
+// Launch the AppImage briefly; an early non-zero exit counts as a crash.
+// Anything still running after the window is considered healthy and stopped.
+fn smoke_test_launch(path: &Path) -> Result<(), String> {
+    use std::io::Read;
+    const SMOKE_TEST_WINDOW: std::time::Duration = std::time::Duration::from_secs(3);
+    
+    info!("Test-launching {}", path.display());
+    let mut child = Command::new(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Couldn't start {}: {}", path.display(), e))?;
+    
+    // Drain stderr as it comes, a full pipe would stall the app
+    let (sender, stderr_receiver) = mpsc::channel();
+    if let Some(mut stderr) = child.stderr.take() {
+        std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stderr.read_to_end(&mut output);
+            let _ = sender.send(output);
+        });
+    }
+    
+    let started = std::time::Instant::now();
+    while started.elapsed() < SMOKE_TEST_WINDOW {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                // A process the app left behind can hold the pipe open, so don't wait forever
+                let output = stderr_receiver.recv_timeout(std::time::Duration::from_secs(1)).unwrap_or_default();
+                let stderr = String::from_utf8_lossy(&output);
+                let stderr = stderr.trim();
+                // Keep the tail, that's where the actual failure usually is
+                let tail: Vec<&str> = stderr.lines().rev().take(10).collect();
+                let tail: Vec<&str> = tail.into_iter().rev().collect();
+                return Err(format!("Exited with {} after {:.1}s\n{}", status, started.elapsed().as_secs_f32(), tail.join("\n")));
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    
+    info!("Test launch still running after {:?}, stopping it", SMOKE_TEST_WINDOW);
+    let _ = child.kill();
+    let _ = child.wait();
+    Ok(())
+}
+
+// Check for both the FUSE kernel device and the libfuse2 userspace library
+fn detect_fuse() -> bool {
+    if !Path::new("/dev/fuse").exists() {
+        return false;
+    }
+    
+    // Prefer the linker cache, fall back to the usual library locations
+    if let Ok(output) = Command::new("ldconfig").arg("-p").output() {
+        if String::from_utf8_lossy(&output.stdout).contains("libfuse.so.2") {
+            return true;
+        }
+    }
+    
+    [
+        "/usr/lib/libfuse.so.2",
+        "/usr/lib64/libfuse.so.2",
+        "/lib/x86_64-linux-gnu/libfuse.so.2",
+        "/usr/lib/x86_64-linux-gnu/libfuse.so.2",
+        "/usr/lib/aarch64-linux-gnu/libfuse.so.2",
+    ]
+    .iter()
+    .any(|path| Path::new(path).exists())
+}
+
+// Human readable byte count, e.g. "148.3 MB"
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+// Short duration for status messages, e.g. "850 ms" or "4.2s"
+fn format_duration(duration: std::time::Duration) -> String {
+    if duration.as_secs() == 0 {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f32())
+    }
+}
+
+// Top-level window ids from the window manager's client list, via xprop
+fn list_client_windows() -> Result<Vec<String>, String> {
+    let output = Command::new("xprop")
+        .args(["-root", "_NET_CLIENT_LIST"])
+        .output()
+        .map_err(|e| format!("Couldn't run xprop: {}", e))?;
+    if !output.status.success() {
+        return Err("xprop couldn't read the window list".to_string());
+    }
+    // _NET_CLIENT_LIST(WINDOW): window id # 0x1a00003, 0x2c00007
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .split_once('#')
+        .map(|(_, ids)| ids.split(',').map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect())
+        .unwrap_or_default())
+}
+
+// The class part of a window's WM_CLASS, which is what StartupWMClass matches
+fn window_class(window: &str) -> Result<String, String> {
+    let output = Command::new("xprop")
+        .args(["-id", window, "WM_CLASS"])
+        .output()
+        .map_err(|e| format!("Couldn't run xprop: {}", e))?;
+    // WM_CLASS(STRING) = "instance", "Class"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .nth(1)
+        .map(str::to_string)
+        .ok_or_else(|| format!("Window {} has no WM_CLASS", window))
+}
+
+// Unix seconds as a UTC calendar date, e.g. "2025-03-14"
+fn format_date(timestamp: u64) -> String {
+    // Civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Shorten long text by replacing its middle with an ellipsis, keeping the end
+// (usually the filename) visible
+fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max_chars || max_chars < 5 {
+        return text.to_string();
+    }
+    
+    let head_len = (max_chars - 1) / 3;
+    let tail_len = max_chars - 1 - head_len;
+    let head: String = chars[..head_len].iter().collect();
+    let tail: String = chars[chars.len() - tail_len..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+// Decode an icon into a thumbnail of at most 64x64: PNG/JPG through the image crate,
+// SVG rasterized with resvg
+fn load_icon_thumbnail(path: &Path) -> Result<egui::ColorImage, String> {
+    const SIZE: u32 = 64;
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+        let tree = resvg::usvg::Tree::from_data(&bytes, &resvg::usvg::Options::default())
+            .map_err(|e| e.to_string())?;
+        let size = tree.size();
+        let scale = SIZE as f32 / size.width().max(size.height());
+        let width = ((size.width() * scale).round() as u32).max(1);
+        let height = ((size.height() * scale).round() as u32).max(1);
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)
+            .ok_or_else(|| "invalid SVG size".to_string())?;
+        resvg::render(&tree, resvg::tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+        return Ok(egui::ColorImage::from_rgba_premultiplied([width as usize, height as usize], pixmap.data()));
+    }
+    
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| e.to_string())?
+        .thumbnail(SIZE, SIZE)
+        .to_rgba8();
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        [image.width() as usize, image.height() as usize],
+        image.as_raw(),
+    ))
+}
+
+// Text, background and border colors of the status frame for a theme and status kind.
+// update() always sets the dark theme for now; the light variant is there for when
+// the theme becomes selectable.
+fn status_palette(dark_mode: bool, level: StatusLevel) -> (Color32, Color32, Color32) {
+    if dark_mode {
+        match level {
+            StatusLevel::Success => (Color32::from_rgb(180, 255, 180), Color32::from_rgb(25, 45, 30), Color32::from_rgb(60, 120, 80)),
+            StatusLevel::Error => (Color32::from_rgb(255, 180, 180), Color32::from_rgb(45, 25, 30), Color32::from_rgb(120, 60, 80)),
+            StatusLevel::Warning => (Color32::from_rgb(255, 220, 150), Color32::from_rgb(45, 35, 20), Color32::from_rgb(120, 90, 40)),
+            StatusLevel::Info => (Color32::from_rgb(220, 220, 220), Color32::from_rgb(35, 35, 45), Color32::from_rgb(70, 70, 90)),
+        }
+    } else {
+        // Dark text on pale backgrounds so the status stays readable on a light theme
+        match level {
+            StatusLevel::Success => (Color32::from_rgb(20, 90, 35), Color32::from_rgb(225, 245, 228), Color32::from_rgb(110, 180, 125)),
+            StatusLevel::Error => (Color32::from_rgb(140, 25, 35), Color32::from_rgb(250, 228, 230), Color32::from_rgb(200, 110, 120)),
+            StatusLevel::Warning => (Color32::from_rgb(120, 75, 0), Color32::from_rgb(252, 240, 215), Color32::from_rgb(210, 165, 80)),
+            StatusLevel::Info => (Color32::from_rgb(40, 40, 50), Color32::from_rgb(240, 240, 245), Color32::from_rgb(170, 170, 185)),
+        }
+    }
+}
+
+// Rename, falling back to copy and delete when the two paths are on different filesystems
+fn move_file(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+// Keep a copy of a file that's about to be replaced. A hard link costs nothing and
+// keeps the old contents once the new file is renamed over the original; a
+// symlink is recreated rather than followed.
+fn snapshot_file(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    if fs::symlink_metadata(to).is_ok() {
+        fs::remove_file(to)?;
+    }
+    if fs::symlink_metadata(from)?.file_type().is_symlink() {
+        return std::os::unix::fs::symlink(fs::read_link(from)?, to);
+    }
+    // Linking fails across filesystems, e.g. a custom install dir on another drive
+    if fs::hard_link(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).map(|_| ())
+}
+
+// Drop the binary an undo snapshot kept aside, once it can no longer be restored
+fn discard_undo_backup(undo: &UndoState) {
+    if let Some(backup) = &undo.binary_backup {
+        if let Err(e) = fs::remove_file(backup) {
+            warn!("Couldn't remove undo backup {}: {}", backup.display(), e);
+        }
+    }
+}
+
+// Unpack the icons and desktop file bundled in an AppImage into a private
+// temporary directory. This runs the AppImage, so it's only done on request.
+fn extract_bundled(appimage: &Path) -> std::io::Result<BundledContents> {
+    let dir = tempfile::Builder::new().prefix("deskimage-").tempdir()?;
+    let desktop = bundled_desktop_entry(appimage, dir.path());
+    let icons = extract_all_icons(appimage, dir.path());
+    Ok(BundledContents { dir, icons, desktop })
+}
+
+// The desktop file at the root of an AppImage, unpacked into `dest`. It's usually
+// a symlink into usr/share/applications, so the target is unpacked too.
+fn bundled_desktop_entry(appimage: &Path, dest: &Path) -> Option<DesktopEntry> {
+    let root = match appimage::extract(appimage, OsStr::new("*.desktop"), dest) {
+        Ok(root) => root,
+        Err(e) => {
+            warn!("Couldn't extract the desktop file from {}: {}", appimage.display(), e);
+            return None;
+        }
+    };
+    let mut desktop_file = fs::read_dir(&root).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "desktop"))?;
+    if let Ok(target) = fs::read_link(&desktop_file) {
+        let target = target.strip_prefix("/").unwrap_or(&target).to_path_buf();
+        if let Err(e) = appimage::extract(appimage, target.as_os_str(), dest) {
+            warn!("Couldn't extract {}: {}", target.display(), e);
+        }
+        desktop_file = root.join(target);
+    }
+    
+    match DesktopEntry::from_file(&desktop_file) {
+        Ok(entry) => {
+            debug!("Read bundled desktop file {}", desktop_file.display());
+            Some(entry)
+        }
+        Err(e) => {
+            warn!("Couldn't read bundled desktop file {}: {}", desktop_file.display(), e);
+            None
+        }
+    }
+}
+
+// Unpack the icons bundled in an AppImage into `dest`: the top-level icon and the
+// app icons under usr/share/icons. Sorted so the SVG, then the largest PNG, comes first.
+fn extract_all_icons(appimage: &Path, dest: &Path) -> Vec<PathBuf> {
+    for pattern in ["*.png", "*.svg", "usr/share/icons/*"] {
+        if let Err(e) = appimage::extract(appimage, OsStr::new(pattern), dest) {
+            warn!("Couldn't extract {} from {}: {}", pattern, appimage.display(), e);
+        }
+    }
+    
+    let mut icons = Vec::new();
+    collect_icon_files(&dest.join("squashfs-root"), &mut icons);
+    icons.sort_by_key(|icon| {
+        let is_svg = icon.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        std::cmp::Reverse((is_svg, png_width(icon).unwrap_or(0)))
+    });
+    debug!("Found {} bundled icons in {}", icons.len(), appimage.display());
+    icons
+}
+
+// PNG and SVG files below a directory that look like app icons: anything at the
+// top level, and only the apps/ directories of icon themes. Symlinks such as
+// .DirIcon point at a file that's already listed.
+fn collect_icon_files(dir: &Path, icons: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_icon_files(&path, icons);
+        } else if file_type.is_file() {
+            let is_icon = path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("png") || ext.eq_ignore_ascii_case("svg"));
+            let in_theme = path.components().any(|c| c.as_os_str() == "icons");
+            let app_icon = !in_theme || path.parent().is_some_and(|parent| parent.ends_with("apps"));
+            if is_icon && app_icon {
+                icons.push(path);
+            }
+        }
+    }
+}
+
+// Width from a PNG's IHDR chunk, without decoding the image
+fn png_width(path: &Path) -> Option<u32> {
+    use std::io::Read;
+    
+    let mut header = [0u8; 24];
+    fs::File::open(path).ok()?.read_exact(&mut header).ok()?;
+    if &header[..8] != b"\x89PNG\r\n\x1a\n" || &header[12..16] != b"IHDR" {
+        return None;
+    }
+    Some(u32::from_be_bytes([header[16], header[17], header[18], header[19]]))
+}
+
+// Whether `copy` looks like an unchanged copy of `source`: the same size and
+// modification time, like rsync's quick check. Cheap enough for the UI thread,
+// since install_copy gives every copy its source's modification time.
+fn is_unchanged_copy(source: &Path, copy: &Path) -> bool {
+    let (Ok(source), Ok(copy)) = (fs::metadata(source), fs::metadata(copy)) else {
+        return false;
+    };
+    source.len() == copy.len()
+        && source.modified().ok().is_some_and(|modified| copy.modified().ok() == Some(modified))
+}
+
+// The original naming: everything before the first '-' or '_'. Still the
+// fallback when the cleaned-up name comes out empty.
+pub fn clean_app_name_legacy(filename: &str) -> String {
+    let base = filename
+        .trim_end_matches(".AppImage")
+        .split(|c: char| c == '-' || c == '_')
+        .next()
+        .unwrap_or(filename);
+    base.to_string()
+}
+
+// Filesystem-friendly form of an app name for the desktop file and binary: no '/',
+// control characters or leading dots, and whitespace runs become '-'. Name= keeps
+// the readable name.
+fn safe_filename(name: &str) -> String {
+    let joined = name.split_whitespace().collect::<Vec<_>>().join("-");
+    let cleaned: String = joined.chars()
+        .filter(|c| *c != '/' && !c.is_control())
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "appimage".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+// A binary name typed by hand must stay inside the install directory. Empty means the default.
+fn is_valid_bin_name(name: &str) -> bool {
+    !name.contains(['/', '\0']) && name != "." && name != ".."
+}
+
+fn desktop_file_name(appname: &str) -> String {
+    format!("{}.desktop", safe_filename(appname))
+}
+
+// Run a command as root, e.g. run_elevated(&[OsStr::new("cp"), src, dst]).
+// pkexec shows a graphical password dialog; sudo is only a fallback since
+// without a terminal it can't ask for the password.
+fn run_elevated(args: &[&OsStr]) -> Result<(), AppError> {
+    info!("Running with elevated rights: {:?}", args);
+    let status = match Command::new("pkexec").args(args).status() {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            warn!("pkexec not found, falling back to sudo");
+            Command::new("sudo").args(args).status()
+        }
+        result => result,
+    }?;
+    if status.success() {
+        Ok(())
+    } else if authentication_cancelled(&status) {
+        Err(AppError::ElevationCancelled)
+    } else {
+        Err(AppError::Elevation(status.to_string()))
+    }
+}
+
+fn copy_with_privilege(src: &Path, dst: &Path) -> Result<(), AppError> {
+    run_elevated(&[OsStr::new("cp"), src.as_os_str(), dst.as_os_str()])
+}
+
+// pkexec exits with 126 when the dialog is dismissed and 127 when authentication fails
+fn authentication_cancelled(status: &std::process::ExitStatus) -> bool {
+    matches!(status.code(), Some(126) | Some(127))
+}
+
+// Check that files can be created in a directory by creating and removing a probe file
+fn check_dir_writable(dir: &Path) -> Result<(), std::io::Error> {
+    let probe = dir.join(format!(".deskimage-write-test-{}", std::process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+// Point `link` at `source` (by absolute path), replacing any file or link already there
+fn replace_with_symlink(source: &Path, link: &Path) -> Result<(), std::io::Error> {
+    let source = fs::canonicalize(source)?;
+    if fs::symlink_metadata(link).is_ok() {
+        fs::remove_file(link)?;
+    }
+    std::os::unix::fs::symlink(&source, link)
+}
+
+// Hex SHA-256 of a file, read in chunks so large AppImages don't need to fit in memory.
+// Setting `cancel_flag` stops it between chunks.
+fn sha256_file(path: &Path, cancel_flag: &AtomicBool) -> Result<String, std::io::Error> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+    
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "checksum cancelled"));
+        }
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// The smallest standard hicolor size an image fits in without scaling, or the
+// largest one for bigger images
+fn hicolor_size(dimension: u32) -> u32 {
+    HICOLOR_SIZES.iter()
+        .copied()
+        .find(|&size| size >= dimension)
+        .unwrap_or(HICOLOR_SIZES[HICOLOR_SIZES.len() - 1])
+}
+
+// Scale an image down to fit `size` if needed, keeping its aspect ratio, and
+// center it on a transparent square canvas
+fn fit_to_square(image: &image::DynamicImage, size: u32) -> image::RgbaImage {
+    let image = if image.width() > size || image.height() > size {
+        image.resize(size, size, image::imageops::FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+    let mut canvas = image::RgbaImage::new(size, size);
+    let x = (size - image.width()) / 2;
+    let y = (size - image.height()) / 2;
+    image::imageops::overlay(&mut canvas, &image.to_rgba8(), x as i64, y as i64);
+    canvas
+}
+
+// Guess the window class by launching the app and looking for the window it opens.
+// Uses xprop, so this only works on X11 (or XWayland windows). Blocks for up to
+// 10 seconds, so it runs on a thread.
+fn detect_wm_class(path: &Path) -> Result<String, String> {
+    const DETECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+    
+    let before = list_client_windows()?;
+    
+    info!("Launching {} to detect its window class", path.display());
+    let mut child = Command::new(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Couldn't start {}: {}", path.display(), e))?;
+    
+    let started = std::time::Instant::now();
+    let mut result = Err("No new window appeared within 10 seconds".to_string());
+    while started.elapsed() < DETECT_TIMEOUT {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        let new_window = list_client_windows()?
+            .into_iter()
+            .find(|window| !before.contains(window));
+        if let Some(window) = new_window {
+            result = window_class(&window);
+            break;
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            result = Err(format!("The app exited with {} before opening a window", status));
+            break;
+        }
+    }
+    
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+// The first of `candidates` whose binary has the same SHA-256 as `source`
+fn find_copy_by_checksum(source: &Path, source_checksum: Option<String>, candidates: Vec<InstalledEntry>) -> Option<InstalledEntry> {
+    let never = AtomicBool::new(false);
+    let source_checksum = match source_checksum {
+        Some(checksum) => checksum,
+        None => sha256_file(source, &never).ok()?,
+    };
+    candidates.into_iter()
+        .find(|entry| sha256_file(Path::new(&entry.desktop.exec), &never).is_ok_and(|checksum| checksum == source_checksum))
+}
+
+// Compare a finished copy with its source by SHA-256, deleting the copy if they
+// differ. The source hash is computed unless it's already known.
+fn verify_copy(source: &Path, dest: &Path, source_checksum: Option<String>, cancel_flag: &AtomicBool) -> Result<(), std::io::Error> {
+    let source_checksum = match source_checksum {
+        Some(checksum) => checksum,
+        None => sha256_file(source, cancel_flag)?,
+    };
+    let dest_checksum = sha256_file(dest, cancel_flag)?;
+    if source_checksum == dest_checksum {
+        info!(dest = %dest.display(), "Copy verified");
+        return Ok(());
+    }
+    
+    warn!("Copy {} doesn't match its source ({} vs {})", dest.display(), dest_checksum, source_checksum);
+    if let Err(e) = fs::remove_file(dest) {
+        warn!("Couldn't remove bad copy {}: {}", dest.display(), e);
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "the copy doesn't match the source (SHA-256 differs), it was deleted",
+    ))
+}
+
+// Copy a binary next to its destination under a temporary name, then rename it into
+// place, so an interrupted copy never leaves a truncated executable at `dest`
+fn install_copy(source: &Path, dest: &Path, sender: &mpsc::Sender<CopyMessage>, cancel_flag: &AtomicBool, verify: Option<Option<String>>) -> Result<u64, std::io::Error> {
+    let tmp_path = tmp_sibling(dest);
+    let result = copy_with_progress(source, &tmp_path, sender, cancel_flag).and_then(|bytes| {
+        if let Some(source_checksum) = verify {
+            let _ = sender.send(CopyMessage::Verifying);
+            verify_copy(source, &tmp_path, source_checksum, cancel_flag)?;
+        }
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o755))?;
+        // Lets the next Create recognize the copy as unchanged without reading it
+        fs::File::options().write(true).open(&tmp_path)?.set_modified(fs::metadata(source)?.modified()?)?;
+        fs::rename(&tmp_path, dest)?;
+        Ok(bytes)
+    });
+    if result.is_err() && fs::symlink_metadata(&tmp_path).is_ok() {
+        if let Err(e) = fs::remove_file(&tmp_path) {
+            warn!("Couldn't remove temporary copy {}: {}", tmp_path.display(), e);
+        }
+    }
+    result
+}
+
+// `<name>.tmp` in the same directory, so renaming it over `path` is atomic
+fn tmp_sibling(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+// Stream a file to its destination, reporting (bytes copied, total) as it goes
+fn copy_with_progress(source: &Path, dest: &Path, sender: &mpsc::Sender<CopyMessage>, cancel_flag: &AtomicBool) -> Result<u64, std::io::Error> {
+    use std::io::{Read, Write};
+    
+    let total = fs::metadata(source)?.len();
+    let mut reader = std::io::BufReader::new(fs::File::open(source)?);
+    let mut writer = std::io::BufWriter::new(fs::File::create(dest)?);
+    let mut buffer = vec![0u8; 256 * 1024];
+    let mut copied = 0u64;
+    
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+        if cancel_flag.load(Ordering::Relaxed) {
+            // Don't leave a truncated executable behind
+            drop(writer);
+            if let Err(e) = fs::remove_file(dest) {
+                warn!("Couldn't remove partial copy {}: {}", dest.display(), e);
+            }
+            return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "copy cancelled"));
+        }
+        // The receiver only goes away if the window closed, nothing to do then
+        let _ = sender.send(CopyMessage::Progress(copied, total));
+    }
+    
+    writer.flush()?;
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_app_name_drops_version_and_platform() {
+        assert_eq!(clean_app_name("Some_App-2.3.1-x86_64.AppImage"), "Some App");
+        assert_eq!(clean_app_name("GIMP-2.10.AppImage"), "GIMP");
+        assert_eq!(clean_app_name("Visual_Studio_Code.AppImage"), "Visual Studio Code");
+    }
+
+    #[test]
+    fn clean_app_name_strips_stacked_platform_suffixes() {
+        assert_eq!(clean_app_name("Foo-linux-x86_64.AppImage"), "Foo");
+        assert_eq!(clean_app_name("foo-bar-1.0-linux-x86_64.AppImage"), "Foo Bar");
+    }
+
+    #[test]
+    fn clean_app_name_keeps_names_without_suffixes() {
+        assert_eq!(clean_app_name("Etcher.AppImage"), "Etcher");
+        // A platform name only counts as a suffix after a separator
+        assert_eq!(clean_app_name("x86_64.AppImage"), "X86 64");
+    }
+
+    #[test]
+    fn version_from_path_keeps_prerelease_tags() {
+        assert_eq!(version_from_path(Path::new("/opt/Some_App-2.3.1-x86_64.AppImage")).as_deref(), Some("2.3.1"));
+        assert_eq!(version_from_path(Path::new("App-v1.0.0-beta.2.AppImage")).as_deref(), Some("1.0.0-beta.2"));
+        assert_eq!(version_from_path(Path::new("Visual_Studio_Code.AppImage")), None);
+    }
+
+    // Rough perceived brightness, enough to tell light from dark
+    fn brightness(color: Color32) -> u32 {
+        (299 * color.r() as u32 + 587 * color.g() as u32 + 114 * color.b() as u32) / 1000
+    }
+
+    #[test]
+    fn status_palette_keeps_text_readable_in_both_themes() {
+        for level in [StatusLevel::Info, StatusLevel::Success, StatusLevel::Warning, StatusLevel::Error] {
+            let (text, background, _) = status_palette(true, level);
+            assert!(brightness(text) > brightness(background) + 100, "dark {:?}", level);
+
+            let (text, background, _) = status_palette(false, level);
+            assert!(brightness(background) > brightness(text) + 100, "light {:?}", level);
+        }
+    }
+
+    // Callers hold paths::lock_env() while using the app
+    fn test_app(root: &Path) -> DeskImageApp {
+        std::env::set_var("XDG_CONFIG_HOME", root.join("config"));
+        std::env::set_var("XDG_DATA_HOME", root.join("data"));
+        let mut app = DeskImageApp::new(AppDirs {
+            bin: root.join("bin"),
+            share: root.join("data"),
+            applications: root.join("data/applications"),
+            icons: root.join("data/icons"),
+        });
+        app.settings.notifications_disabled = true;
+        app
+    }
+
+    #[test]
+    fn pending_create_runs_once_on_the_next_frame() {
+        let _env = paths::lock_env();
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = egui::Context::default();
+        let mut app = test_app(dir.path());
+        app.appimage_path = Some(dir.path().join("Missing.AppImage"));
+
+        app.request_create(&ctx);
+        assert!(app.pending_create);
+        assert!(app.work_in_progress());
+        assert_eq!(app.create_disabled_reason().as_deref(), Some("Already creating the desktop entry"));
+        assert_eq!(app.status, (StatusLevel::Info, "Processing...".to_string()));
+
+        app.run_pending_create(&ctx);
+        assert!(!app.pending_create);
+        assert!(!app.work_in_progress());
+        assert_eq!(app.status.0, StatusLevel::Error);
+
+        // Nothing is pending any more, so the next frame leaves the result alone
+        app.update_status(StatusLevel::Info, "Done".to_string());
+        app.run_pending_create(&ctx);
+        assert_eq!(app.status, (StatusLevel::Info, "Done".to_string()));
+    }
+
+    #[test]
+    fn existing_entry_asks_before_create_is_pending() {
+        let _env = paths::lock_env();
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = egui::Context::default();
+        let mut app = test_app(dir.path());
+        app.app_id = "org.example.Foo".to_string();
+        let existing = app.desktop_file_path("org.example.Foo").unwrap();
+        fs::create_dir_all(existing.parent().unwrap()).unwrap();
+        fs::write(&existing, "[Desktop Entry]\n").unwrap();
+
+        app.request_create(&ctx);
+        assert_eq!(app.pending_overwrite, Some(existing));
+        assert!(!app.pending_create);
+    }
+
+    #[test]
+    fn manage_list_toggles_autostart_copy() {
+        let _env = paths::lock_env();
+        let dir = tempfile::tempdir().unwrap();
+        let app = test_app(dir.path());
+        let path = app.app_dirs.applications.join("foo.desktop");
+        fs::create_dir_all(&app.app_dirs.applications).unwrap();
+        fs::write(&path, "[Desktop Entry]\nName=Foo\nExec=/opt/foo\n").unwrap();
+        let entry = InstalledEntry {
+            desktop: DesktopEntry::from_file(&path).unwrap(),
+            path,
+            added_on: None,
+            autostart: false,
+        };
+        let autostart_path = app.autostart_file_path("foo").unwrap();
+        assert!(autostart_path.starts_with(dir.path()));
+
+        app.set_installed_autostart(&entry, true).unwrap();
+        assert_eq!(fs::read_to_string(&autostart_path).unwrap(), fs::read_to_string(&entry.path).unwrap());
+
+        app.set_installed_autostart(&entry, false).unwrap();
+        assert!(!autostart_path.exists());
+    }
+
+    #[test]
+    fn sha256_file_stops_once_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc");
+        fs::write(&path, "abc").unwrap();
+
+        let cancel_flag = AtomicBool::new(false);
+        assert_eq!(sha256_file(&path, &cancel_flag).unwrap(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        cancel_flag.store(true, Ordering::Relaxed);
+        assert_eq!(sha256_file(&path, &cancel_flag).unwrap_err().kind(), std::io::ErrorKind::Interrupted);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// One install or uninstall, appended to ~/.local/share/deskimage/history.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub action: String,
+    pub appname: String,
+    pub source: Option<PathBuf>,
+    pub dest: PathBuf,
+}
+
+// Next to the installed entries, so it honors XDG_DATA_HOME the same way
+pub fn path() -> Option<PathBuf> {
+    Some(paths::data_dir().ok()?.join("deskimage").join("history.json"))
+}
+
+// A missing or unreadable history counts as empty
+pub fn load() -> Vec<HistoryRecord> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    read(&path).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring invalid history file {}: {}", path.display(), e);
+        Vec::new()
+    })
+}
+
+// Only a missing file counts as empty here
+fn read(path: &Path) -> anyhow::Result<Vec<HistoryRecord>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn append(record: HistoryRecord) -> anyhow::Result<()> {
+    let path = path().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    append_to(&path, record)
+}
+
+fn append_to(path: &Path, record: HistoryRecord) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // The window and `deskimage add`/`purge` can append at the same time; the
+    // lock is released when the file is dropped
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("json.lock"))?;
+    lock.lock()?;
+
+    // A history that doesn't parse is left alone rather than replaced by one record
+    let mut records = read(path)
+        .map_err(|e| anyhow::anyhow!("Not updating invalid history file {}: {}", path.display(), e))?;
+    records.push(record);
+    // Write then rename so a crash never loses the existing history
+    let tmp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&tmp_path, serde_json::to_string_pretty(&records)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(appname: String) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: 0,
+            action: "install".to_string(),
+            dest: PathBuf::from("/opt").join(&appname),
+            appname,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn concurrent_appends_keep_every_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+
+        let writers: Vec<_> = ["window", "cli"]
+            .into_iter()
+            .map(|writer| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for i in 0..20 {
+                        append_to(&path, record(format!("{}-{}", writer, i))).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let records = read(&path).unwrap();
+        assert_eq!(records.len(), 40);
+        for writer in ["window", "cli"] {
+            assert_eq!(records.iter().filter(|record| record.appname.starts_with(writer)).count(), 20);
+        }
+    }
+
+    #[test]
+    fn unparsable_history_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert!(append_to(&path, record("foo".to_string())).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+    }
+}
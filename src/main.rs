@@ -1,155 +1,116 @@
+mod appimage;
+mod cli;
+mod desktop;
+mod error;
 mod gui;
+mod history;
+mod options;
+mod paths;
+mod settings;
+
+use clap::Parser;
+use error::AppError;
+use std::io;
+use std::path::PathBuf;
+
+// The per-user directories DeskImage installs into, resolved once at startup
+#[derive(Debug, Clone)]
+pub struct AppDirs {
+    pub bin: PathBuf,
+    pub share: PathBuf,
+    pub applications: PathBuf,
+    pub icons: PathBuf,
+}
 
 fn main() {
-    // Initialize logging
-    env_logger::init();
+    let args = cli::Cli::parse();
+    
+    // Initialize logging, defaulting to info level unless RUST_LOG says otherwise.
+    // Logs go to stderr so command output stays scriptable.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+    
+    // Without these directories nothing can be installed, so stop here rather
+    // than fail later in a confusing way
+    let app_dirs = match ensure_app_dirs() {
+        Ok(app_dirs) => app_dirs,
+        Err(e) => {
+            tracing::error!("{}", e);
+            if args.command.is_none() {
+                rfd::MessageDialog::new()
+                    .set_level(rfd::MessageLevel::Error)
+                    .set_title("DeskImage")
+                    .set_description(format!("DeskImage can't start: {}", e))
+                    .set_buttons(rfd::MessageButtons::Ok)
+                    .show();
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            std::process::exit(1);
+        }
+    };
     
-    // Ensure application directories exist
-    ensure_app_dirs();
+    // Subcommands run without opening a window
+    if let Some(command) = args.command {
+        std::process::exit(cli::run(command, app_dirs));
+    }
     
     // Launch the GUI version
-    if let Err(e) = gui::run_gui() {
+    if let Err(e) = gui::run_gui(app_dirs) {
         eprintln!("Error running GUI: {}", e);
     }
 }
 
-// Ensure application directories exist
-fn ensure_app_dirs() -> bool {
+// Resolve the application directories and create any that are missing
+fn ensure_app_dirs() -> Result<AppDirs, AppError> {
     use std::fs;
     
-    let home_dir = match dirs::home_dir() {
-        Some(dir) => dir,
-        None => {
-            eprintln!("Could not determine home directory");
-            return false;
-        }
-    };
-    
-    let local_bin = home_dir.join(".local/bin");
-    let local_share = home_dir.join(".local/share");
-    let local_apps = match dirs::data_dir() {
-        Some(dir) => dir.join("applications"),
-        None => home_dir.join(".local/share/applications"),
+    // Not needed when the XDG overrides cover every directory
+    match paths::resolve_home() {
+        Ok(home) => tracing::info!("Home directory: {}", home.display()),
+        Err(e) => tracing::warn!("{}", e),
+    }
+    let app_dirs = AppDirs {
+        bin: paths::bin_dir()?,
+        share: paths::data_dir()?,
+        applications: paths::applications_dir()?,
+        icons: paths::icons_dir()?,
     };
-    let local_icons = home_dir.join(".local/share/icons");
     
-    println!("Local bin directory: {}", local_bin.display());
-    println!("Local share directory: {}", local_share.display());
-    println!("Applications directory: {}", local_apps.display());
-    println!("Icons directory: {}", local_icons.display());
+    tracing::info!("Local bin directory: {}", app_dirs.bin.display());
+    tracing::info!("Local share directory: {}", app_dirs.share.display());
+    tracing::info!("Applications directory: {}", app_dirs.applications.display());
+    tracing::info!("Icons directory: {}", app_dirs.icons.display());
     
     // Create directories if they don't exist
-    let directories = vec![
-        ("bin", local_bin),
-        ("share", local_share),
-        ("applications", local_apps),
-        ("icons", local_icons),
+    let directories = [
+        ("bin", &app_dirs.bin),
+        ("share", &app_dirs.share),
+        ("applications", &app_dirs.applications),
+        ("icons", &app_dirs.icons),
     ];
     
     for (name, path) in directories {
         if !path.exists() {
-            println!("Creating {} directory: {}", name, path.display());
-            if let Err(e) = fs::create_dir_all(&path) {
-                eprintln!("Error creating {} directory: {}", name, e);
-                continue;
-            }
+            tracing::info!("Creating {} directory: {}", name, path.display());
+            fs::create_dir_all(path).map_err(|e| {
+                AppError::Io(io::Error::new(e.kind(), format!("couldn't create {} directory {}: {}", name, path.display(), e)))
+            })?;
         }
     }
     
     // Check for environment variables
-    if let Some(val) = std::env::var_os("XDG_DATA_HOME") {
-        println!("XDG_DATA_HOME is set to: {:?}", val);
-    } else {
-        println!("XDG_DATA_HOME is not set");
-    }
-    
-    true
-}
-
-// CLI implementation, now unused
-#[allow(dead_code)]
-fn run_cli() {
-    use std::env;
-    use std::fs;
-    use std::io::{self};
-    use std::os::unix::fs::PermissionsExt;
-    use std::path::{Path, PathBuf};
-    use std::process::Command;
-
-    let current_exe = env::current_exe().unwrap();
-    let target_path = Path::new("/usr/local/bin/deskimage");
-
-    if current_exe != target_path {
-        println!("📦 DeskImage is not installed globally.");
-        println!("⚙️  Do you want to install it to /usr/local/bin? [y/N]");
-
-        let mut choice = String::new();
-        io::stdin().read_line(&mut choice).unwrap();
-
-        if choice.trim().to_lowercase() == "y" {
-            let status = Command::new("sudo")
-                .arg("cp")
-                .arg(&current_exe)
-                .arg(target_path)
-                .status()
-                .expect("Failed to execute sudo cp");
-
-            if status.success() {
-                println!("✅ Installed to /usr/local/bin. Now you can run `deskimage` globally.");
-            } else {
-                eprintln!("❌ Failed to install. Are you sure you have sudo?");
-            }
-            return;
+    for var in ["XDG_DATA_HOME", "XDG_BIN_HOME"] {
+        match std::env::var_os(var) {
+            Some(val) => tracing::info!("{} is set to: {:?}", var, val),
+            None => tracing::info!("{} is not set", var),
         }
     }
-
-    println!("🖼️  Enter path to your AppImage file:");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let appimage_path = PathBuf::from(input.trim());
-
-    if !appimage_path.exists() {
-        eprintln!("❌ File not found.");
-        return;
-    }
-
-    let original_name = appimage_path.file_name().unwrap().to_string_lossy();
-    let appname = clean_app_name(&original_name);
-
-    let exec_target = dirs::home_dir().unwrap().join(".local/bin").join(&appname);
-
-    fs::create_dir_all(exec_target.parent().unwrap()).unwrap();
-    fs::copy(&appimage_path, &exec_target).unwrap();
-    fs::set_permissions(&exec_target, fs::Permissions::from_mode(0o755)).unwrap();
-
-    let desktop_content = format!(
-        "[Desktop Entry]
-Type=Application
-Name={}
-Exec={}
-Icon=application-x-executable
-Terminal=false
-Categories=Utility;
-",
-        appname,
-        exec_target.to_string_lossy()
-    );
-
-    let desktop_file_path = dirs::data_dir()
-        .unwrap()
-        .join("applications")
-        .join(format!("{}.desktop", appname));
-    fs::create_dir_all(desktop_file_path.parent().unwrap()).unwrap();
-    fs::write(&desktop_file_path, desktop_content).unwrap();
-
-    println!("✅ Desktop entry created at: {}", desktop_file_path.display());
-}
-
-fn clean_app_name(filename: &str) -> String {
-    let base = filename
-        .trim_end_matches(".AppImage")
-        .split(|c: char| c == '-' || c == '_')
-        .next()
-        .unwrap_or(filename);
-    base.to_string()
+    
+    Ok(app_dirs)
 }
@@ -0,0 +1,64 @@
+use crate::desktop::DesktopAction;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// How the executable ends up where Exec= points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InstallMode {
+    // A copy in the install directory
+    #[default]
+    Copy,
+    // A symlink in the install directory to the original
+    Symlink,
+    // Exec= points at the original, nothing goes into the install directory
+    InPlace,
+}
+
+// Everything needed to reproduce an install, saved as `<name>.deskimage.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstallOptions {
+    pub source: PathBuf,
+    pub name: String,
+    pub app_id: String,
+    pub icon: Option<PathBuf>,
+    pub categories: Vec<String>,
+    pub generic_name: String,
+    pub comment: String,
+    pub keywords: String,
+    pub mime_types: String,
+    pub terminal: bool,
+    pub exec_args: String,
+    pub startup_wm_class: String,
+    pub actions: Vec<DesktopAction>,
+    pub autostart: bool,
+    pub keep_original_filename: bool,
+    pub bin_name: String,
+    pub install_mode: InstallMode,
+    // Where the binary went; files from before this was saved use the current setting
+    pub install_dir: Option<PathBuf>,
+    pub system_wide: bool,
+}
+
+impl InstallOptions {
+    // Suggested file name for exporting these options
+    pub fn default_file_name(&self) -> String {
+        let stem = self.source
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "install".to_string());
+        format!("{}.deskimage.toml", stem)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
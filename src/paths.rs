@@ -0,0 +1,109 @@
+use crate::error::AppError;
+use std::env;
+use std::path::PathBuf;
+
+// Per-user install locations. Each honors its XDG override and falls back to
+// the usual directory under ~/.local. Without a home directory (e.g. a
+// container with neither HOME nor a passwd entry) and no override, they fail
+// with an error that can be shown to the user.
+
+pub fn resolve_home() -> Result<PathBuf, AppError> {
+    dirs::home_dir().ok_or(AppError::NoHome)
+}
+
+// XDG_DATA_HOME, or ~/.local/share
+pub fn data_dir() -> Result<PathBuf, AppError> {
+    xdg_dir("XDG_DATA_HOME", resolve_home(), ".local/share")
+}
+
+// XDG_DATA_HOME/applications, or ~/.local/share/applications
+pub fn applications_dir() -> Result<PathBuf, AppError> {
+    Ok(data_dir()?.join("applications"))
+}
+
+// XDG_DATA_HOME/icons, or ~/.local/share/icons
+pub fn icons_dir() -> Result<PathBuf, AppError> {
+    Ok(data_dir()?.join("icons"))
+}
+
+// XDG_BIN_HOME, or ~/.local/bin
+pub fn bin_dir() -> Result<PathBuf, AppError> {
+    xdg_dir("XDG_BIN_HOME", resolve_home(), ".local/bin")
+}
+
+// The directory an XDG variable names, or the default under home. Like the
+// spec says, a relative override is ignored.
+fn xdg_dir(var: &str, home: Result<PathBuf, AppError>, default: &str) -> Result<PathBuf, AppError> {
+    match env::var_os(var).map(PathBuf::from).filter(|dir| dir.is_absolute()) {
+        Some(dir) => Ok(dir),
+        None => Ok(home?.join(default)),
+    }
+}
+
+// Tests run on parallel threads but share one environment, so any test that
+// sets or reads HOME or XDG_* holds this for its whole run
+#[cfg(test)]
+pub(crate) fn lock_env() -> std::sync::MutexGuard<'static, ()> {
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xdg_overrides_are_honored() {
+        let _env = lock_env();
+        env::set_var("XDG_DATA_HOME", "/tmp/deskimage-test/data");
+        env::set_var("XDG_BIN_HOME", "/tmp/deskimage-test/bin");
+
+        assert_eq!(data_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/data"));
+        assert_eq!(applications_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/data/applications"));
+        assert_eq!(icons_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/data/icons"));
+        assert_eq!(bin_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/bin"));
+
+        env::remove_var("XDG_DATA_HOME");
+        env::remove_var("XDG_BIN_HOME");
+    }
+
+    #[test]
+    fn defaults_are_under_local() {
+        let _env = lock_env();
+        env::remove_var("XDG_DATA_HOME");
+        env::remove_var("XDG_BIN_HOME");
+        let home = env::var_os("HOME");
+        env::set_var("HOME", "/tmp/deskimage-test/home");
+
+        assert_eq!(data_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/home/.local/share"));
+        assert_eq!(applications_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/home/.local/share/applications"));
+        assert_eq!(bin_dir().unwrap(), PathBuf::from("/tmp/deskimage-test/home/.local/bin"));
+
+        match home {
+            Some(home) => env::set_var("HOME", home),
+            None => env::remove_var("HOME"),
+        }
+    }
+
+    #[test]
+    fn missing_home_is_an_error_without_an_override() {
+        let _env = lock_env();
+        env::remove_var("XDG_DATA_HOME");
+        assert!(matches!(xdg_dir("XDG_DATA_HOME", Err(AppError::NoHome), ".local/share"), Err(AppError::NoHome)));
+
+        // An override still works without a home directory
+        env::set_var("XDG_DATA_HOME", "/tmp/deskimage-test/data");
+        let dir = xdg_dir("XDG_DATA_HOME", Err(AppError::NoHome), ".local/share");
+        env::remove_var("XDG_DATA_HOME");
+        assert_eq!(dir.unwrap(), PathBuf::from("/tmp/deskimage-test/data"));
+    }
+
+    #[test]
+    fn relative_overrides_are_ignored() {
+        let _env = lock_env();
+        env::set_var("XDG_BIN_HOME", "relative/bin");
+        let dir = xdg_dir("XDG_BIN_HOME", Ok(PathBuf::from("/home/me")), ".local/bin");
+        env::remove_var("XDG_BIN_HOME");
+        assert_eq!(dir.unwrap(), PathBuf::from("/home/me/.local/bin"));
+    }
+}
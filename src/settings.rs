@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+// How many recently selected files are remembered
+const MAX_RECENT_FILES: usize = 10;
+
+// User preferences remembered between runs, stored in ~/.config/deskimage/config.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window_size: Option<[f32; 2]>,
+    pub last_appimage_dir: Option<PathBuf>,
+    pub last_icon_dir: Option<PathBuf>,
+    pub install_dir: Option<PathBuf>,
+    // Most recent first
+    pub recent_files: VecDeque<PathBuf>,
+    // Desktop notifications are on unless turned off here
+    pub notifications_disabled: bool,
+    // Removed executables go to the trash unless turned off here
+    pub trash_disabled: bool,
+    // Compare SHA-256 of source and copy after copying; off since it reads both files again
+    pub verify_copy: bool,
+    // Whether the Advanced section of the form was left open
+    pub advanced_expanded: bool,
+}
+
+impl Settings {
+    // Honors XDG_CONFIG_HOME through dirs::config_dir
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("deskimage").join("config.json"))
+    }
+
+    // Missing or unreadable settings fall back to the defaults
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let mut settings: Self = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid settings file {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        
+        // Forget files that were moved or deleted since, and any duplicates
+        let mut seen = Vec::new();
+        settings.recent_files.retain(|file| {
+            let keep = file.exists() && !seen.contains(file);
+            seen.push(file.clone());
+            keep
+        });
+        settings.recent_files.truncate(MAX_RECENT_FILES);
+        settings
+    }
+    
+    // Move a file to the front of the recent files, dropping the oldest beyond the limit
+    pub fn remember_recent_file(&mut self, file: PathBuf) {
+        self.recent_files.retain(|recent| *recent != file);
+        self.recent_files.push_front(file);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash never leaves a half-written config behind
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}